@@ -0,0 +1,316 @@
+//! Running many days in one go, optionally in parallel, and summarizing the outcome.
+
+use std::{
+    any::Any,
+    error::Error,
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc2022::{AnswerKind, Diagnostics, Input, Params, Part};
+
+use crate::{output::Record, solver};
+
+/// Answers and timings of one day, up to the first error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayReport {
+    pub day: u8,
+    /// The answers that were found before an error, if any.
+    pub records: Vec<Record>,
+    pub error: Option<String>,
+    /// Wall time of parsing and solving both parts.
+    pub time: Duration,
+}
+
+impl DayReport {
+    fn answer(&self, part: Part) -> Option<&Record> {
+        self.records.iter().find(|record| record.part == part)
+    }
+
+    /// A record for both parts, where a part without an answer holds the error of the day,
+    /// for [`crate::output::write_records`].
+    pub fn results(&self) -> Vec<Record> {
+        [Part::One, Part::Two]
+            .into_iter()
+            .filter_map(|part| match (self.answer(part), &self.error) {
+                (Some(record), _) => Some(record.clone()),
+                (None, None) => None,
+                (None, Some(error)) => Some(Record {
+                    day: self.day,
+                    part,
+                    answer: String::new(),
+                    kind: solver(self.day)
+                        .map_or(AnswerKind::Text, |solver| solver.answer_kind(part)),
+                    parse_time: Duration::ZERO,
+                    solve_time: Duration::ZERO,
+                    error: Some(error.clone()),
+                }),
+            })
+            .collect()
+    }
+}
+
+fn solve_day(
+    day: u8,
+    input: &Input,
+    params: &Params,
+    records: &mut Vec<Record>,
+) -> Result<(), Box<dyn Error>> {
+    let solver = solver(day).ok_or_else(|| format!("no solution for day {}", day))?;
+    let start = Instant::now();
+    let parsed = solver.parse_input(input, &mut Diagnostics::default())?;
+    let parse_time = start.elapsed();
+    for part in [Part::One, Part::Two] {
+        let start = Instant::now();
+        let answer = solver.solve(parsed.as_ref(), part, params)?;
+        records.push(Record {
+            day,
+            part,
+            answer,
+            kind: solver.answer_kind(part),
+            parse_time,
+            solve_time: start.elapsed(),
+            error: None,
+        });
+    }
+    Ok(())
+}
+
+/// Renders the payload of a caught panic as an error message.
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => format!("panicked: {}", message),
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => format!("panicked: {}", message),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+/// Parses `input` and solves both parts of `day`, turning errors and panics into a report.
+pub fn run_day(day: u8, input: &Input, params: &Params) -> DayReport {
+    let start = Instant::now();
+    let mut records = vec![];
+    let error = match panic::catch_unwind(AssertUnwindSafe(|| {
+        solve_day(day, input, params, &mut records).map_err(|err| err.to_string())
+    })) {
+        Ok(result) => result.err(),
+        Err(payload) => Some(panic_message(payload)),
+    };
+    DayReport {
+        day,
+        records,
+        error,
+        time: start.elapsed(),
+    }
+}
+
+/// Runs every day on its input with its parameters, each on its own thread if `parallel` is
+/// set.
+///
+/// A failing day does not stop the others; reports are in the order of `days`.
+pub fn run_all(days: &[(u8, Input, Params)], parallel: bool) -> Vec<DayReport> {
+    if !parallel {
+        return days
+            .iter()
+            .map(|(day, input, params)| run_day(*day, input, params))
+            .collect();
+    }
+    thread::scope(|scope| {
+        let handles = days
+            .iter()
+            .map(|(day, input, params)| scope.spawn(move || run_day(*day, input, params)))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("panics are caught by run_day"))
+            .collect()
+    })
+}
+
+/// Shortens an answer to one line, so that it fits in a table cell.
+fn summarize(record: Option<&Record>) -> String {
+    match record {
+        None => "-".to_string(),
+        Some(record) if record.answer.contains('\n') => {
+            format!("({}, {} lines)", record.kind, record.answer.lines().count())
+        }
+        Some(record) => record.answer.clone(),
+    }
+}
+
+/// Prints a table of answers and timings with a totals row, followed by any multi-line
+/// answers in full.
+///
+/// `wall_time` is the time the whole run took, which is less than the sum of the days when
+/// they ran in parallel.
+pub fn print_summary<W>(mut writer: W, reports: &[DayReport], wall_time: Duration) -> io::Result<()>
+where
+    W: Write,
+{
+    let cells = reports
+        .iter()
+        .map(|report| {
+            (
+                summarize(report.answer(Part::One)),
+                summarize(report.answer(Part::Two)),
+            )
+        })
+        .collect::<Vec<_>>();
+    let width = |column: fn(&(String, String)) -> &String, header: &str| {
+        cells
+            .iter()
+            .map(|cell| column(cell).len())
+            .max()
+            .unwrap_or(0)
+            .max(header.len())
+    };
+    let width1 = width(|(part1, _)| part1, "part 1");
+    let width2 = width(|(_, part2)| part2, "part 2");
+
+    writeln!(
+        writer,
+        "{:>3}  {:<width1$}  {:<width2$}  {:>10}  status",
+        "day", "part 1", "part 2", "time"
+    )?;
+    for (report, (part1, part2)) in reports.iter().zip(&cells) {
+        let status = match &report.error {
+            None => "ok",
+            Some(err) => err.lines().next().unwrap_or_default(),
+        };
+        writeln!(
+            writer,
+            "{:>3}  {:<width1$}  {:<width2$}  {:>10}  {}",
+            report.day,
+            part1,
+            part2,
+            format!("{:.1?}", report.time),
+            status
+        )?;
+    }
+
+    let failed = reports
+        .iter()
+        .filter(|report| report.error.is_some())
+        .count();
+    let total = reports.iter().map(|report| report.time).sum::<Duration>();
+    writeln!(
+        writer,
+        "{:>3}  {:<width1$}  {:<width2$}  {:>10}  {} ok, {} failed, {:.1?} wall time",
+        "all",
+        "",
+        "",
+        format!("{:.1?}", total),
+        reports.len() - failed,
+        failed,
+        wall_time
+    )?;
+
+    for record in reports.iter().flat_map(|report| &report.records) {
+        if record.answer.contains('\n') {
+            writeln!(
+                writer,
+                "\nday {} part {}:\n{}",
+                record.day, record.part, record.answer
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(day: u8) -> Input<'static> {
+        Input::path(match day {
+            1 | 2 => format!("../day{:02}/test_input.txt", day),
+            _ => format!("../day{:02}/input_test.txt", day),
+        })
+    }
+
+    #[test]
+    fn parallel_run_matches_sequential_run() {
+        let days = [1, 8, 9].map(|day| (day, example(day), Params::default()));
+        let answers = |reports: Vec<DayReport>| {
+            reports
+                .into_iter()
+                .map(|report| {
+                    assert_eq!(report.error, None, "day {} failed", report.day);
+                    let answers = report.records.into_iter().map(|record| record.answer);
+                    (report.day, answers.collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>()
+        };
+        let sequential = answers(run_all(&days, false));
+        assert_eq!(sequential[1], (8, vec!["21".to_string(), "8".to_string()]));
+        assert_eq!(answers(run_all(&days, true)), sequential);
+    }
+
+    #[test]
+    fn failures_do_not_stop_other_days() {
+        let days = [
+            (2, Input::text("A Y\nB Q\n"), Params::default()),
+            (0, example(1), Params::default()),
+            (1, example(1), Params::default()),
+        ];
+        let reports = run_all(&days, true);
+        assert!(reports[0].error.as_ref().unwrap().contains("found `Q`"));
+        assert_eq!(reports[1].error.as_deref(), Some("no solution for day 0"));
+        assert_eq!(reports[2].error, None);
+        assert_eq!(reports[2].records.len(), 2);
+
+        let results = reports.iter().map(DayReport::results).collect::<Vec<_>>();
+        assert_eq!(results[2], reports[2].records);
+        assert_eq!(results[0].len(), 2);
+        assert!(results[0].iter().all(|record| record.answer.is_empty()));
+        assert_eq!(results[0][1].error, reports[0].error);
+        assert_eq!(results[0][1].kind, AnswerKind::Integer);
+    }
+
+    #[test]
+    fn summary_has_totals_and_full_grids() {
+        let record = |part, answer: &str, kind| Record {
+            day: 10,
+            part,
+            answer: answer.to_string(),
+            kind,
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+            error: None,
+        };
+        let reports = [
+            DayReport {
+                day: 10,
+                records: vec![
+                    record(Part::One, "13140", aoc2022::AnswerKind::Integer),
+                    record(Part::Two, "#.\n.#", aoc2022::AnswerKind::Grid),
+                ],
+                error: None,
+                time: Duration::from_millis(2),
+            },
+            DayReport {
+                day: 11,
+                records: vec![],
+                error: Some("day11/input.txt: no such file\nmore".to_string()),
+                time: Duration::from_millis(1),
+            },
+        ];
+
+        let mut output = vec![];
+        print_summary(&mut output, &reports, Duration::from_millis(2)).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "day  part 1  part 2                 time  status\n \
+             10  13140   (grid, 2 lines)       2.0ms  ok\n \
+             11  -       -                     1.0ms  day11/input.txt: no such file\n\
+             all                                3.0ms  1 ok, 1 failed, 2.0ms wall time\n\
+             \n\
+             day 10 part 2:\n\
+             #.\n\
+             .#\n"
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Timing of the parse and solve stages of each day, on stable Rust.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    hint::black_box,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use aoc2022::{
+    parser::{number, one_of, pair, parse_line, preceded, tag},
+    Diagnostics, Input, Params, Part,
+};
+
+use crate::solver;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub const ALL: [Self; 3] = [Self::Parse, Self::Part1, Self::Part2];
+
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("parse", Self::Parse),
+        ("part1", Self::Part1),
+        ("part2", Self::Part2),
+    ];
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (name, _) = Self::NAMES.iter().find(|(_, stage)| stage == self).unwrap();
+        f.pad(name)
+    }
+}
+
+/// Summary of the durations of repeated runs of a stage.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarizes `samples`, using the nearest rank for percentiles.
+    ///
+    /// # Panics
+    ///
+    /// If there are no samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort();
+        let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Self {
+            min: samples[0],
+            median: percentile(50),
+            p95: percentile(95),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+fn time<T, F>(iterations: usize, mut f: F) -> Result<Stats, Box<dyn Error>>
+where
+    F: FnMut() -> Result<T, Box<dyn Error>>,
+{
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        black_box(f()?);
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(samples))
+}
+
+/// Runs every stage of `day` on `input` for `iterations` times each.
+///
+/// The input is held in memory, so that parsing is timed without reading the file.
+pub fn bench_day(
+    day: u8,
+    input: &str,
+    iterations: usize,
+) -> Result<Vec<Measurement>, Box<dyn Error>> {
+    let solver = solver(day).ok_or_else(|| format!("no solution for day {}", day))?;
+    let parse = || solver.parse_input(&Input::text(input), &mut Diagnostics::default());
+    let parsed = parse()?;
+
+    Stage::ALL
+        .into_iter()
+        .map(|stage| {
+            let stats = match stage {
+                Stage::Parse => time(iterations, parse)?,
+                Stage::Part1 => time(iterations, || {
+                    solver.solve(parsed.as_ref(), Part::One, &Params::default())
+                })?,
+                Stage::Part2 => time(iterations, || {
+                    solver.solve(parsed.as_ref(), Part::Two, &Params::default())
+                })?,
+            };
+            Ok(Measurement { day, stage, stats })
+        })
+        .collect()
+}
+
+/// Median durations of an earlier run, to compare new measurements against.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Baseline(HashMap<(u8, Stage), Duration>);
+
+impl Baseline {
+    pub fn new(measurements: &[Measurement]) -> Self {
+        Self(
+            measurements
+                .iter()
+                .map(|m| ((m.day, m.stage), m.stats.median))
+                .collect(),
+        )
+    }
+
+    pub fn median(&self, day: u8, stage: Stage) -> Option<Duration> {
+        self.0.get(&(day, stage)).copied()
+    }
+
+    /// Reads a baseline written by [`Baseline::write`], one `day stage nanoseconds` per line.
+    pub fn read(input: &Input) -> Result<Self, Box<dyn Error>> {
+        let mut medians = HashMap::new();
+        let lines = input.lines().map_err(|err| format!("{}: {}", input, err))?;
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (day, (stage, nanos)) = parse_line(
+                index + 1,
+                &line,
+                pair(
+                    number(),
+                    pair(
+                        preceded(tag(" "), one_of(Stage::NAMES)),
+                        preceded(tag(" "), number()),
+                    ),
+                ),
+            )
+            .map_err(|err| err.in_file(input.to_string()))?;
+            medians.insert((day, stage), Duration::from_nanos(nanos));
+        }
+        Ok(Self(medians))
+    }
+
+    pub fn write<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let mut entries = self.0.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(key, _)| **key);
+
+        writeln!(writer, "# day stage median_ns")?;
+        for ((day, stage), median) in entries {
+            writeln!(writer, "{} {} {}", day, stage, median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// Change of `median` relative to `baseline`, like `+12.5%`.
+fn change(median: Duration, baseline: Duration) -> String {
+    let change = median.as_secs_f64() / baseline.as_secs_f64() - 1.0;
+    format!("{:+.1}%", change * 100.0)
+}
+
+/// Prints measurements as a table, comparing medians to `baseline` if there is one.
+pub fn print_report<W>(
+    mut writer: W,
+    measurements: &[Measurement],
+    baseline: Option<&Baseline>,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    write!(
+        writer,
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}",
+        "day", "stage", "min", "median", "p95"
+    )?;
+    if baseline.is_some() {
+        write!(writer, "  {:>10}  {:>8}", "baseline", "change")?;
+    }
+    writeln!(writer)?;
+
+    for &Measurement { day, stage, stats } in measurements {
+        write!(
+            writer,
+            "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}",
+            day,
+            stage,
+            format!("{:.1?}", stats.min),
+            format!("{:.1?}", stats.median),
+            format!("{:.1?}", stats.p95)
+        )?;
+        match baseline.map(|baseline| baseline.median(day, stage)) {
+            Some(Some(median)) => write!(
+                writer,
+                "  {:>10}  {:>8}",
+                format!("{:.1?}", median),
+                change(stats.median, median)
+            )?,
+            Some(None) => write!(writer, "  {:>10}  {:>8}", "-", "-")?,
+            None => {}
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_use_nearest_rank() {
+        let samples = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+
+        let stats = Stats::from_samples(vec![Duration::from_millis(3)]);
+        assert_eq!((stats.median, stats.p95), (stats.min, stats.min));
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let stats = Stats {
+            min: Duration::from_micros(1),
+            median: Duration::from_micros(2),
+            p95: Duration::from_micros(3),
+        };
+        let baseline = Baseline::new(&[
+            Measurement {
+                day: 8,
+                stage: Stage::Part2,
+                stats,
+            },
+            Measurement {
+                day: 1,
+                stage: Stage::Parse,
+                stats,
+            },
+        ]);
+
+        let mut file = vec![];
+        baseline.write(&mut file).unwrap();
+        let file = String::from_utf8(file).unwrap();
+        assert_eq!(file, "# day stage median_ns\n1 parse 2000\n8 part2 2000\n");
+        assert_eq!(Baseline::read(&Input::text(&file)).unwrap(), baseline);
+    }
+}
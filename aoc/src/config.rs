@@ -0,0 +1,153 @@
+//! Parameters of the solutions, from a TOML file and from the command line.
+
+use std::{collections::BTreeMap, error::Error, io::Read};
+
+use aoc2022::{Input, Params};
+
+use crate::solver;
+
+/// Values of parameters by day, checked against the solutions when they are used.
+///
+/// In a file, every day is a table of values, like
+///
+/// ```toml
+/// [day10]
+/// crt_width = 20
+/// signal_cycles = [10, 30]
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config(BTreeMap<u8, Vec<(String, String)>>);
+
+/// Renders a TOML value in the syntax the parameters parse, with lists separated by commas.
+fn render(value: &toml::Value) -> Result<String, String> {
+    Ok(match value {
+        toml::Value::String(value) => value.clone(),
+        toml::Value::Integer(value) => value.to_string(),
+        toml::Value::Float(value) => value.to_string(),
+        toml::Value::Boolean(value) => value.to_string(),
+        toml::Value::Array(values) => values
+            .iter()
+            .map(render)
+            .collect::<Result<Vec<_>, _>>()?
+            .join(","),
+        _ => return Err(format!("unsupported value `{}`", value)),
+    })
+}
+
+impl Config {
+    pub fn read(input: &Input) -> Result<Self, Box<dyn Error>> {
+        let mut text = String::new();
+        input
+            .open()
+            .and_then(|mut reader| reader.read_to_string(&mut text))
+            .map_err(|err| format!("{}: {}", input, err))?;
+        Ok(Self::parse(&text).map_err(|err| format!("{}: {}", input, err))?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table = text.parse::<toml::Table>().map_err(|err| err.to_string())?;
+        let mut config = Self::default();
+        for (key, values) in table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| format!("expected a table like `[day01]`, found `{}`", key))?;
+            let values = values
+                .as_table()
+                .ok_or_else(|| format!("expected `{}` to be a table of parameters", key))?;
+            for (name, value) in values {
+                let value = render(value).map_err(|err| format!("`{}.{}`: {}", key, name, err))?;
+                config.set(day, name, &value);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Sets a parameter of `day`, overriding an earlier value.
+    pub fn set(&mut self, day: u8, name: &str, value: &str) {
+        self.0
+            .entry(day)
+            .or_default()
+            .push((name.to_string(), value.to_string()));
+    }
+
+    /// The parameters of `day`, after checking them against its solution.
+    pub fn params(&self, day: u8) -> Result<Params, Box<dyn Error>> {
+        let solver = solver(day).ok_or_else(|| format!("no solution for day {}", day))?;
+        let mut params = Params::default();
+        for (name, value) in self.0.get(&day).into_iter().flatten() {
+            params
+                .set(solver.parameters(), name, value)
+                .map_err(|err| format!("day {}: {}", day, err))?;
+        }
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc2022::Part;
+
+    use super::*;
+
+    #[test]
+    fn parameters_change_answers() {
+        let mut config = Config::parse("[day01]\ntop = 2\n").unwrap();
+        let solver = solver(1).unwrap();
+        let input = solver
+            .parse_input(
+                &Input::path("../day01/test_input.txt"),
+                &mut Default::default(),
+            )
+            .unwrap();
+        let answer = |config: &Config| {
+            solver
+                .solve(input.as_ref(), Part::Two, &config.params(1).unwrap())
+                .unwrap()
+        };
+        assert_eq!(answer(&config), "35000");
+        config.set(1, "top", "1");
+        assert_eq!(answer(&config), "24000");
+        assert_eq!(answer(&Config::default()), "45000");
+    }
+
+    #[test]
+    fn lists_are_separated_by_commas() {
+        let config = Config::parse("[day10]\nsignal_cycles = [20, 60]\ncrt_width = 20").unwrap();
+        assert!(config.params(10).is_ok());
+        assert_eq!(
+            config.0[&10],
+            [
+                ("crt_width".to_string(), "20".to_string()),
+                ("signal_cycles".to_string(), "20,60".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_parameters_are_reported() {
+        let config = Config::parse("[day11]\nrelief = 0").unwrap();
+        let err = config.params(11).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "day 11: invalid value `0` for `relief`: number would be zero for non-zero type"
+        );
+        assert!(Config::parse("[eleven]\nrounds = 1").is_err());
+        assert!(Config::parse("day11 = 1").is_err());
+        assert!(Config::parse("[day11\n").is_err());
+    }
+
+    #[test]
+    fn every_default_is_valid() {
+        for day in crate::DAYS {
+            for parameter in solver(day).unwrap().parameters() {
+                assert_eq!(
+                    parameter.check(parameter.default_value()),
+                    Ok(()),
+                    "day {}",
+                    day
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Downloading puzzle inputs into a cache, so that they do not have to be committed.
+//!
+//! Inputs are personal, so they are fetched with the session token of the user and kept
+//! outside of the tree. A cached input is never downloaded again.
+
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The site inputs are fetched from by default.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Sends the requests of a [`Fetcher`], so that tests can stand in for the site.
+pub trait Http {
+    /// Gets the body at `url`, authenticating with the `session` cookie.
+    fn get(&self, url: &str, session: &str) -> Result<String, Box<dyn Error>>;
+
+    /// Posts `form` to `url`, authenticating with the `session` cookie, and returns the body
+    /// of the response.
+    fn post(
+        &self,
+        url: &str,
+        session: &str,
+        form: &[(&str, &str)],
+    ) -> Result<String, Box<dyn Error>>;
+}
+
+/// The body of `response`, or the body of the error response along with its status.
+fn body(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, Box<dyn Error>> {
+    let response = response.map_err(|err| match err {
+        ureq::Error::Status(status, response) => format!(
+            "{}: status {}: {}",
+            url,
+            status,
+            response.into_string().unwrap_or_default().trim()
+        ),
+        ureq::Error::Transport(err) => format!("{}: {}", url, err),
+    })?;
+    Ok(response
+        .into_string()
+        .map_err(|err| format!("{}: {}", url, err))?)
+}
+
+fn request(method: &str, url: &str, session: &str) -> ureq::Request {
+    ureq::request(method, url)
+        .set("Cookie", &format!("session={}", session))
+        .set(
+            "User-Agent",
+            concat!("aoc2022-runner/", env!("CARGO_PKG_VERSION")),
+        )
+}
+
+/// Sends requests with [`ureq`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ureq;
+
+impl Http for Ureq {
+    fn get(&self, url: &str, session: &str) -> Result<String, Box<dyn Error>> {
+        body(url, request("GET", url, session).call())
+    }
+
+    fn post(
+        &self,
+        url: &str,
+        session: &str,
+        form: &[(&str, &str)],
+    ) -> Result<String, Box<dyn Error>> {
+        body(url, request("POST", url, session).send_form(form))
+    }
+}
+
+/// Whether [`Fetcher::fetch`] had to download the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    /// The input was in the cache already, nothing was downloaded.
+    Cached(PathBuf),
+}
+
+/// Default cache directory: `$AOC_CACHE_DIR`, or `aoc2022` in the user's cache directory.
+pub fn default_cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("AOC_CACHE_DIR") {
+        return Some(dir.into());
+    }
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .map(|dir| dir.join("aoc2022"))
+}
+
+/// Where the input of `day` is cached in `cache_dir`, like `day03.txt`.
+pub fn cached_input(cache_dir: &Path, day: u8) -> PathBuf {
+    cache_dir.join(format!("day{:02}.txt", day))
+}
+
+/// Downloads inputs into a cache directory.
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    http: Box<dyn Http>,
+}
+
+impl Fetcher {
+    pub fn new<P>(session: &str, cache_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            base_url: BASE_URL.to_string(),
+            session: session.trim().to_string(),
+            cache_dir: cache_dir.into(),
+            http: Box::new(Ureq),
+        }
+    }
+
+    /// Fetches from another site, like a local mock of it.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sends the requests with `http` instead of [`Ureq`].
+    pub fn with_http<H>(mut self, http: H) -> Self
+    where
+        H: Http + 'static,
+    {
+        self.http = Box::new(http);
+        self
+    }
+
+    /// Downloads the input of `day` unless it is cached already.
+    ///
+    /// The input is written to a temporary file first, so that a failed download never
+    /// leaves a partial input in the cache.
+    pub fn fetch(&self, day: u8) -> Result<Fetched, Box<dyn Error>> {
+        let path = cached_input(&self.cache_dir, day);
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+        if self.session.is_empty() {
+            return Err("a session token is needed to fetch inputs".into());
+        }
+
+        let url = format!("{}/2022/day/{}/input", self.base_url, day);
+        let input = self.http.get(&url, &self.session)?;
+        if input.trim().is_empty() {
+            return Err(format!("{}: the input is empty", url).into());
+        }
+
+        let in_dir = |err| format!("{}: {}", self.cache_dir.display(), err);
+        fs::create_dir_all(&self.cache_dir).map_err(in_dir)?;
+        let partial = path.with_extension("txt.partial");
+        fs::write(&partial, input).map_err(in_dir)?;
+        fs::rename(&partial, &path).map_err(in_dir)?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+    };
+
+    use tiny_http::{Response, Server};
+
+    use super::*;
+
+    /// A fresh cache directory for a test.
+    fn cache_dir(test: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Records the requests and answers them with a canned input.
+    #[derive(Clone, Default)]
+    struct Mock(Rc<RefCell<Vec<(String, String)>>>);
+
+    impl Http for Mock {
+        fn get(&self, url: &str, session: &str) -> Result<String, Box<dyn Error>> {
+            self.0
+                .borrow_mut()
+                .push((url.to_string(), session.to_string()));
+            Ok("1000\n2000\n".to_string())
+        }
+
+        fn post(&self, url: &str, _: &str, _: &[(&str, &str)]) -> Result<String, Box<dyn Error>> {
+            Err(format!("{}: fetching never posts", url).into())
+        }
+    }
+
+    #[test]
+    fn cached_inputs_are_not_fetched_again() {
+        let dir = cache_dir("cached");
+        let mock = Mock::default();
+        let fetcher = Fetcher::new("secret\n", &dir).with_http(mock.clone());
+
+        let path = dir.join("day03.txt");
+        assert_eq!(fetcher.fetch(3).unwrap(), Fetched::Downloaded(path.clone()));
+        assert_eq!(fetcher.fetch(3).unwrap(), Fetched::Cached(path.clone()));
+        assert_eq!(
+            *mock.0.borrow(),
+            [(
+                "https://adventofcode.com/2022/day/3/input".to_string(),
+                "secret".to_string()
+            )]
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn fetching_needs_a_session() {
+        let dir = cache_dir("session");
+        let err = Fetcher::new("", &dir).fetch(1).unwrap_err();
+        assert_eq!(err.to_string(), "a session token is needed to fetch inputs");
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn inputs_are_fetched_over_http() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+        let requests = AtomicUsize::new(0);
+        let dir = cache_dir("http");
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for request in server.incoming_requests() {
+                    requests.fetch_add(1, Ordering::Relaxed);
+                    let cookie = request
+                        .headers()
+                        .iter()
+                        .find(|header| header.field.equiv("Cookie"))
+                        .map(|header| header.value.to_string());
+                    let response = match (request.url(), cookie.as_deref()) {
+                        ("/2022/day/1/input", Some("session=secret")) => {
+                            Response::from_string("A Y\n")
+                        }
+                        (_, Some("session=secret")) => {
+                            Response::from_string("not unlocked yet").with_status_code(404)
+                        }
+                        _ => Response::from_string("log in").with_status_code(400),
+                    };
+                    request.respond(response).unwrap();
+                }
+            });
+
+            let fetcher = Fetcher::new("secret", &dir).with_base_url(&base_url);
+            assert!(matches!(fetcher.fetch(1), Ok(Fetched::Downloaded(_))));
+            let err = fetcher.fetch(2).unwrap_err().to_string();
+            assert!(err.ends_with("status 404: not unlocked yet"), "{}", err);
+            let err = Fetcher::new("stolen", &dir)
+                .with_base_url(&base_url)
+                .fetch(2)
+                .unwrap_err();
+            assert!(err.to_string().ends_with("status 400: log in"));
+            assert!(matches!(fetcher.fetch(1), Ok(Fetched::Cached(_))));
+            server.unblock();
+        });
+
+        assert_eq!(requests.into_inner(), 3);
+        assert_eq!(fs::read_to_string(dir.join("day01.txt")).unwrap(), "A Y\n");
+        assert!(!dir.join("day02.txt").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+use std::{ops::RangeInclusive, path::Path};
+
+use aoc2022::{detect::SAMPLE_LINES, Detect, Generate, Input, Solver};
+
+pub mod all;
+pub mod bench;
+pub mod config;
+pub mod fetch;
+pub mod output;
+pub mod repl;
+pub mod server;
+pub mod snapshot;
+pub mod submit;
+pub mod verify;
+
+/// All days that have a solution.
+pub const DAYS: RangeInclusive<u8> = 1..=11;
+
+/// Looks up the solver for the specified day.
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    Some(match day {
+        1 => &day01::Day01,
+        2 => &day02::Day02,
+        3 => &day03::Day03,
+        4 => &day04::Day04,
+        5 => &day05::Day05,
+        6 => &day06::Day06,
+        7 => &day07::Day07,
+        8 => &day08::Day08,
+        9 => &day09::Day09,
+        10 => &day10::Day10,
+        11 => &day11::Day11,
+        _ => return None,
+    })
+}
+
+/// Looks up the input generator for the specified day.
+pub fn generator(day: u8) -> Option<&'static dyn Generate> {
+    Some(match day {
+        1 => &day01::Day01,
+        2 => &day02::Day02,
+        3 => &day03::Day03,
+        4 => &day04::Day04,
+        5 => &day05::Day05,
+        6 => &day06::Day06,
+        7 => &day07::Day07,
+        8 => &day08::Day08,
+        9 => &day09::Day09,
+        10 => &day10::Day10,
+        11 => &day11::Day11,
+        _ => return None,
+    })
+}
+
+/// Looks up the recognizer of inputs for the specified day.
+pub fn detector(day: u8) -> Option<&'static dyn Detect> {
+    Some(match day {
+        1 => &day01::Day01,
+        2 => &day02::Day02,
+        3 => &day03::Day03,
+        4 => &day04::Day04,
+        5 => &day05::Day05,
+        6 => &day06::Day06,
+        7 => &day07::Day07,
+        8 => &day08::Day08,
+        9 => &day09::Day09,
+        10 => &day10::Day10,
+        11 => &day11::Day11,
+        _ => return None,
+    })
+}
+
+/// How confident every day is that `sample` is its input, the most likely day first.
+pub fn identify(sample: &str) -> Vec<(u8, f64)> {
+    let mut days = DAYS
+        .filter_map(|day| Some((day, detector(day)?.detect(sample))))
+        .collect::<Vec<_>>();
+    days.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    days
+}
+
+/// Checks that `input` looks like the input of `day` rather than of another day.
+///
+/// Fails if it clearly belongs to another day and returns a warning if it might. Inputs
+/// that cannot be read twice, like stdin, or cannot be read at all are not checked.
+pub fn check_input(day: u8, input: &Input) -> Result<Option<String>, String> {
+    if *input == Input::Stdin {
+        return Ok(None);
+    }
+    let Ok(lines) = input.lines() else {
+        return Ok(None);
+    };
+    let sample = lines
+        .take(SAMPLE_LINES)
+        .map_while(Result::ok)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let days = identify(&sample);
+    let confidence = |wanted| {
+        days.iter()
+            .find(|&&(day, _)| day == wanted)
+            .map_or(0.0, |&(_, confidence)| confidence)
+    };
+    let own = confidence(day);
+    let Some(&(likely, other)) = days.iter().find(|&&(other, _)| other != day) else {
+        return Ok(None);
+    };
+    if own >= 0.9 || other <= own {
+        Ok(None)
+    } else if own < 0.5 && other >= 0.9 {
+        Err(format!(
+            "{} looks like the input of day {}, not day {}",
+            input, likely, day
+        ))
+    } else {
+        Ok(Some(format!(
+            "{} might be the input of day {} rather than day {}",
+            input, likely, day
+        )))
+    }
+}
+
+/// Default location of a day's puzzle input: `dayNN/input.txt` relative to the workspace
+/// root, or the fetched input if there is none in the tree.
+pub fn default_input(day: u8) -> String {
+    let path = format!("day{:02}/input.txt", day);
+    if Path::new(&path).exists() {
+        return path;
+    }
+    fetch::default_cache_dir()
+        .map(|dir| fetch::cached_input(&dir, day))
+        .filter(|cached| cached.exists())
+        .map_or(path, |cached| cached.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc2022::{Diagnostics, Params, Part};
+
+    use super::*;
+
+    #[test]
+    fn every_day_has_a_solver() {
+        for day in DAYS {
+            assert!(solver(day).is_some(), "missing solver for day {}", day);
+        }
+        assert!(solver(0).is_none());
+        assert!(solver(*DAYS.end() + 1).is_none());
+    }
+
+    #[test]
+    fn inputs_are_recognized() {
+        for day in DAYS {
+            let mut inputs =
+                vec![fs::read_to_string(format!("../day{:02}/input.txt", day)).unwrap()];
+            inputs.extend((0..3).map(|seed| generator(day).unwrap().generate_seeded(seed, 20)));
+            for input in inputs {
+                let sample = input
+                    .lines()
+                    .take(SAMPLE_LINES)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let days = identify(&sample);
+                assert_eq!(days[0].0, day, "{:?}\n{}", days, sample);
+                assert!(days[0].1 >= 0.9, "{:?}\n{}", days, sample);
+            }
+        }
+    }
+
+    #[test]
+    fn inputs_of_other_days_are_refused() {
+        assert_eq!(check_input(9, &Input::path("../day09/input.txt")), Ok(None));
+        assert_eq!(
+            check_input(9, &Input::path("../day05/input.txt")),
+            Err("../day05/input.txt looks like the input of day 5, not day 9".to_string())
+        );
+        assert_eq!(
+            check_input(1, &Input::path("../day08/input_test.txt")),
+            Ok(Some(
+                "../day08/input_test.txt might be the input of day 8 rather than day 1".to_string()
+            ))
+        );
+        assert_eq!(check_input(1, &Input::path("missing.txt")), Ok(None));
+    }
+
+    #[test]
+    fn generated_inputs_can_be_solved() {
+        for day in DAYS {
+            let solver = solver(day).unwrap();
+            for seed in 0..3 {
+                let input = generator(day).unwrap().generate_seeded(seed, 20);
+                let result = solver
+                    .parse_input(&Input::text(&input), &mut Diagnostics::default())
+                    .and_then(|parsed| {
+                        solver.solve(parsed.as_ref(), Part::One, &Params::default())?;
+                        solver.solve(parsed.as_ref(), Part::Two, &Params::default())
+                    });
+                if let Err(err) = result {
+                    panic!("day {} seed {}: {}\n{}", day, seed, err, input);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,698 @@
+use std::{
+    env,
+    error::Error,
+    fs::{self, File},
+    io::{self, BufWriter, Read, Write},
+    path::PathBuf,
+    process::ExitCode,
+    time::{Instant, SystemTime},
+};
+
+use aoc::{
+    all::{self, DayReport},
+    bench::{self, Baseline},
+    config::Config,
+    fetch::{self, Fetched, Fetcher},
+    output::{self, Format, Record},
+    repl, server,
+    snapshot::{self, Snapshot},
+    submit::{Submitter, Verdict},
+    verify::{self, Outcome},
+};
+use aoc2022::{
+    trace::{self, Guard, Level, Writer},
+    AnswerKind, Diagnostics, Input, Params, ParseMode, Part,
+};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one or both parts of a day.
+    Run {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=11))]
+        day: u8,
+        /// Part to solve; solves both parts if omitted.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Path to the puzzle input (`.gz` is decompressed), or `-` for stdin. Defaults to
+        /// `dayNN/input.txt`.
+        #[arg(short, long)]
+        input: Option<Input<'static>>,
+        /// Skip malformed input lines with a warning instead of failing.
+        #[arg(long)]
+        lenient: bool,
+        /// Solve the input even if it looks like the input of another day.
+        #[arg(long)]
+        force: bool,
+        /// How to print the answers.
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+        /// Print events of the solution up to this level (`info`, `debug` or `trace`) to
+        /// stderr.
+        #[arg(short, long)]
+        trace: Option<Level>,
+        /// Write the events to this file instead of stderr.
+        #[arg(long, requires = "trace")]
+        trace_file: Option<PathBuf>,
+        /// Set a parameter of the solution, may be repeated; overrides the config file.
+        #[arg(short = 'P', long = "param", value_name = "NAME=VALUE", value_parser = parse_assignment)]
+        params: Vec<(String, String)>,
+        /// TOML file with parameters of the solutions, one table like `[day11]` per day.
+        #[arg(long)]
+        config: Option<Input<'static>>,
+    },
+    /// Time parsing and both parts of one or more days.
+    Bench {
+        /// Day to benchmark, may be repeated; benchmarks all days if omitted.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=11))]
+        day: Vec<u8>,
+        /// Number of runs of every stage.
+        #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        iterations: u64,
+        /// Baseline file to compare the medians against.
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Save the medians as a baseline file.
+        #[arg(long)]
+        save_baseline: Option<PathBuf>,
+    },
+    /// Solve both parts of every day and print a summary, continuing past failures.
+    All {
+        /// Day to solve, may be repeated; solves all days if omitted.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=11))]
+        day: Vec<u8>,
+        /// Solve the days on parallel threads.
+        #[arg(short, long)]
+        parallel: bool,
+        /// How to print the answers; text is a table with a summary.
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+        /// TOML file with parameters of the solutions, one table like `[day11]` per day.
+        #[arg(long)]
+        config: Option<Input<'static>>,
+    },
+    /// List the parameters of the solutions with their defaults.
+    Params {
+        /// Day to list, may be repeated; lists all days if omitted.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=11))]
+        day: Vec<u8>,
+    },
+    /// Download the input of a day into the cache, unless it is cached already.
+    Fetch {
+        /// Day to fetch, may be repeated.
+        #[arg(short, long, required = true, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Vec<u8>,
+        /// Session cookie of the site; read from `AOC_SESSION` if omitted.
+        #[arg(long)]
+        session: Option<String>,
+        /// Directory of the cache; defaults to `$AOC_CACHE_DIR` or `~/.cache/aoc2022`.
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
+    },
+    /// Submit an answer, unless the log of earlier submissions shows it is pointless.
+    Submit {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Answer to submit; solves the part on its input if omitted.
+        #[arg(short, long)]
+        answer: Option<String>,
+        /// Path to the puzzle input to solve. Defaults to `dayNN/input.txt`.
+        #[arg(short, long, conflicts_with = "answer")]
+        input: Option<Input<'static>>,
+        /// Set a parameter of the solution, may be repeated; overrides the config file.
+        #[arg(
+            short = 'P',
+            long = "param",
+            value_name = "NAME=VALUE",
+            value_parser = parse_assignment,
+            conflicts_with = "answer"
+        )]
+        params: Vec<(String, String)>,
+        /// TOML file with parameters of the solutions, one table like `[day11]` per day.
+        #[arg(long, conflicts_with = "answer")]
+        config: Option<Input<'static>>,
+        /// Session cookie of the site; read from `AOC_SESSION` if omitted.
+        #[arg(long)]
+        session: Option<String>,
+        /// Directory of the log of submissions; defaults to `$AOC_CACHE_DIR` or
+        /// `~/.cache/aoc2022`.
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
+    },
+    /// Write a random input for a day, for stress testing.
+    Generate {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=11))]
+        day: u8,
+        /// Seed of the input; a random one is used and printed if omitted.
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Size of the input, like the number of lines or moves, depending on the day.
+        #[arg(short, long, default_value_t = 1000)]
+        size: usize,
+        /// File to write the input to instead of stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Follow the simulation of day 5, 9, 10 or 11 step by step, reading commands like
+    /// `step 10`, `run until cycle >= 20`, `back` and `show` from stdin.
+    Repl {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=11))]
+        day: u8,
+        /// Part whose simulation to follow.
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Path to the puzzle input (`.gz` is decompressed). Defaults to
+        /// `dayNN/input.txt`.
+        #[arg(short, long)]
+        input: Option<Input<'static>>,
+        /// Set a parameter of the solution, may be repeated; overrides the config file.
+        #[arg(short = 'P', long = "param", value_name = "NAME=VALUE", value_parser = parse_assignment)]
+        params: Vec<(String, String)>,
+        /// TOML file with parameters of the solutions, one table like `[day11]` per day.
+        #[arg(long)]
+        config: Option<Input<'static>>,
+    },
+    /// Run the simulation of day 5, 9, 10 or 11 to its end, optionally writing snapshots
+    /// of its state, and show the final state.
+    Simulate {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=11))]
+        day: u8,
+        /// Part whose simulation to run.
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Path to the puzzle input (`.gz` is decompressed), or `-` for stdin. Defaults to
+        /// `dayNN/input.txt`.
+        #[arg(short, long)]
+        input: Option<Input<'static>>,
+        /// Set a parameter of the solution, may be repeated; overrides the config file.
+        #[arg(short = 'P', long = "param", value_name = "NAME=VALUE", value_parser = parse_assignment)]
+        params: Vec<(String, String)>,
+        /// TOML file with parameters of the solutions, one table like `[day11]` per day.
+        #[arg(long)]
+        config: Option<Input<'static>>,
+        /// Resume from a snapshot taken on the same input.
+        #[arg(long)]
+        resume: Option<PathBuf>,
+        /// Write a snapshot every N steps.
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+        snapshot_every: Option<u64>,
+        /// Directory to write the snapshots to.
+        #[arg(long, default_value = ".", requires = "snapshot_every")]
+        snapshot_dir: PathBuf,
+        /// Stop once this many steps are taken, counting those before a resumed snapshot.
+        #[arg(long)]
+        until_step: Option<u64>,
+    },
+    /// Serve the solutions over a local HTTP API.
+    Serve {
+        /// Address to listen on.
+        #[arg(short, long, default_value = "127.0.0.1:8022")]
+        address: String,
+    },
+    /// Check solutions against a registry of known answers.
+    Verify {
+        /// Day to check, may be repeated; checks all registered answers if omitted.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=11))]
+        day: Vec<u8>,
+        /// Registry of answers, one `day part input answer` per line.
+        #[arg(short, long, default_value = "answers.txt")]
+        answers: Input<'static>,
+    },
+}
+
+/// Splits a `NAME=VALUE` argument.
+fn parse_assignment(arg: &str) -> Result<(String, String), String> {
+    arg.split_once('=')
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .ok_or_else(|| format!("expected `NAME=VALUE`, found `{}`", arg))
+}
+
+/// Warns about an input that might belong to another day, and refuses one that clearly
+/// does unless `force` is set.
+fn check_input(day: u8, input: &Input, force: bool) -> Result<(), Box<dyn Error>> {
+    match aoc::check_input(day, input) {
+        Ok(None) => {}
+        Ok(Some(warning)) => eprintln!("warning: {}", warning),
+        Err(err) if force => eprintln!("warning: {}", err),
+        Err(err) => return Err(format!("{}, pass `--force` to solve it anyway", err).into()),
+    }
+    Ok(())
+}
+
+/// Reads the config file, if any, or an empty config.
+fn read_config(config: Option<Input>) -> Result<Config, Box<dyn Error>> {
+    config.map_or_else(|| Ok(Config::default()), |config| Config::read(&config))
+}
+
+fn run(
+    day: u8,
+    part: Option<u8>,
+    input: Option<Input>,
+    mode: ParseMode,
+    force: bool,
+    format: Format,
+    params: &Params,
+) -> Result<(), Box<dyn Error>> {
+    let solver = aoc::solver(day).ok_or_else(|| format!("no solution for day {}", day))?;
+    let input = input.unwrap_or_else(|| Input::path(aoc::default_input(day)));
+    check_input(day, &input, force)?;
+    let mut diagnostics = Diagnostics::new(mode);
+    let start = Instant::now();
+    let input = solver.parse_input(&input, &mut diagnostics)?;
+    let parse_time = start.elapsed();
+    for warning in diagnostics.errors() {
+        eprintln!("warning: {}", warning);
+    }
+    let parts = match part {
+        Some(1) => vec![Part::One],
+        Some(_) => vec![Part::Two],
+        None => vec![Part::One, Part::Two],
+    };
+    // A failing part does not keep the answer of the other from being written.
+    let mut records = vec![];
+    let mut errors = vec![];
+    for part in parts {
+        let start = Instant::now();
+        match solver.solve(input.as_ref(), part, params) {
+            Ok(answer) => records.push(Record {
+                day,
+                part,
+                answer,
+                kind: solver.answer_kind(part),
+                parse_time,
+                solve_time: start.elapsed(),
+                error: None,
+            }),
+            Err(err) => errors.push(format!("part {}: {}", part, err)),
+        }
+    }
+    output::write_records(io::stdout().lock(), format, &records)?;
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n").into())
+    }
+}
+
+/// Installs a sink for the events of the solution, if any were asked for.
+fn install_trace(
+    level: Option<Level>,
+    file: Option<PathBuf>,
+) -> Result<Option<Guard>, Box<dyn Error>> {
+    let Some(level) = level else {
+        return Ok(None);
+    };
+    Ok(Some(match file {
+        Some(path) => {
+            let file = File::create(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+            trace::install(level, Writer(BufWriter::new(file)))
+        }
+        None => trace::install(level, Writer(io::stderr())),
+    }))
+}
+
+fn bench(
+    days: Vec<u8>,
+    iterations: usize,
+    baseline: Option<PathBuf>,
+    save_baseline: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let baseline = match baseline {
+        Some(path) => Some(Baseline::read(&Input::path(path))?),
+        None => None,
+    };
+    let days = if days.is_empty() {
+        aoc::DAYS.collect()
+    } else {
+        days
+    };
+
+    let mut measurements = vec![];
+    for day in days {
+        // Read once up front, so that parsing is timed without opening or decompressing.
+        let input = Input::path(aoc::default_input(day));
+        let mut text = String::new();
+        input
+            .open()
+            .and_then(|mut reader| reader.read_to_string(&mut text))
+            .map_err(|err| format!("{}: {}", input, err))?;
+        measurements.extend(bench::bench_day(day, &text, iterations)?);
+    }
+    bench::print_report(io::stdout().lock(), &measurements, baseline.as_ref())?;
+
+    if let Some(path) = save_baseline {
+        let file = File::create(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Baseline::new(&measurements).write(BufWriter::new(file))?;
+    }
+    Ok(())
+}
+
+fn all(
+    days: Vec<u8>,
+    parallel: bool,
+    format: Format,
+    config: Option<Input>,
+) -> Result<(), Box<dyn Error>> {
+    let config = read_config(config)?;
+    let days = if days.is_empty() {
+        aoc::DAYS.collect()
+    } else {
+        days
+    };
+    let days = days
+        .into_iter()
+        .map(|day| {
+            Ok((
+                day,
+                Input::path(aoc::default_input(day)),
+                config.params(day)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+
+    let start = Instant::now();
+    let reports = all::run_all(&days, parallel);
+    if format == Format::Text {
+        all::print_summary(io::stdout().lock(), &reports, start.elapsed())?;
+    } else {
+        let records = reports
+            .iter()
+            .flat_map(DayReport::results)
+            .collect::<Vec<_>>();
+        output::write_records(io::stdout().lock(), format, &records)?;
+    }
+
+    let failed = reports
+        .iter()
+        .filter(|report| report.error.is_some())
+        .count();
+    if failed > 0 {
+        return Err(format!("{} of {} days failed", failed, reports.len()).into());
+    }
+    Ok(())
+}
+
+fn params(days: Vec<u8>) -> Result<(), Box<dyn Error>> {
+    let days = if days.is_empty() {
+        aoc::DAYS.collect()
+    } else {
+        days
+    };
+    let mut stdout = io::stdout().lock();
+    for day in days {
+        let solver = aoc::solver(day).ok_or_else(|| format!("no solution for day {}", day))?;
+        for parameter in solver.parameters() {
+            writeln!(
+                stdout,
+                "day{:02}.{} = {}  # {}",
+                day,
+                parameter.name(),
+                parameter.default_value(),
+                parameter.description()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// The session token and cache directory given on the command line, or their defaults.
+fn session_and_cache_dir(
+    session: Option<String>,
+    cache_dir: Option<PathBuf>,
+) -> Result<(String, PathBuf), Box<dyn Error>> {
+    let session = session
+        .or_else(|| env::var("AOC_SESSION").ok())
+        .ok_or("no session token, pass `--session` or set `AOC_SESSION`")?;
+    let cache_dir = cache_dir
+        .or_else(fetch::default_cache_dir)
+        .ok_or("no cache directory, pass `--cache-dir` or set `AOC_CACHE_DIR`")?;
+    Ok((session, cache_dir))
+}
+
+fn fetch(
+    days: Vec<u8>,
+    session: Option<String>,
+    cache_dir: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let (session, cache_dir) = session_and_cache_dir(session, cache_dir)?;
+    let fetcher = Fetcher::new(&session, cache_dir);
+    for day in days {
+        match fetcher.fetch(day)? {
+            Fetched::Downloaded(path) => println!("day {}: fetched {}", day, path.display()),
+            Fetched::Cached(path) => println!("day {}: cached {}", day, path.display()),
+        }
+    }
+    Ok(())
+}
+
+fn repl(day: u8, part: Part, input: Option<Input>, params: &Params) -> Result<(), Box<dyn Error>> {
+    let input = input.unwrap_or_else(|| Input::path(aoc::default_input(day)));
+    if input == Input::Stdin {
+        return Err("commands are read from stdin, so the input has to be a file".into());
+    }
+    check_input(day, &input, false)?;
+    let mut session = repl::session(day, &input, part, params)?;
+    repl::run(session.as_mut(), io::stdin().lock(), io::stdout().lock())
+}
+
+fn simulate(
+    day: u8,
+    part: Part,
+    input: Option<Input>,
+    params: &Params,
+    resume: Option<PathBuf>,
+    snapshots: Option<(usize, PathBuf)>,
+    until_step: Option<usize>,
+) -> Result<(), Box<dyn Error>> {
+    let input = input.unwrap_or_else(|| Input::path(aoc::default_input(day)));
+    check_input(day, &input, false)?;
+    let mut session = repl::session(day, &input, part, params)?;
+    if let Some(path) = resume {
+        let snapshot = Snapshot::read(&path)?;
+        session
+            .restore(snapshot)
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+    }
+    let (every, dir) = snapshots.map_or((None, PathBuf::new()), |(every, dir)| (Some(every), dir));
+    let written = snapshot::simulate(session.as_mut(), every, &dir, until_step)?;
+    println!("{}", session.execute("show")?);
+    if every.is_some() {
+        eprintln!("wrote {} snapshots to {}", written.len(), dir.display());
+    }
+    Ok(())
+}
+
+fn submit(
+    day: u8,
+    part: Part,
+    answer: Option<String>,
+    input: Option<Input>,
+    config: &Config,
+    session: Option<String>,
+    cache_dir: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let (session, cache_dir) = session_and_cache_dir(session, cache_dir)?;
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solver = aoc::solver(day).ok_or_else(|| format!("no solution for day {}", day))?;
+            if solver.answer_kind(part) == AnswerKind::Grid {
+                return Err("grid answers have to be read off and passed with `--answer`".into());
+            }
+            let input = input.unwrap_or_else(|| Input::path(aoc::default_input(day)));
+            check_input(day, &input, false)?;
+            let input = solver.parse_input(&input, &mut Diagnostics::default())?;
+            solver.solve(input.as_ref(), part, &config.params(day)?)?
+        }
+    };
+
+    let mut submitter = Submitter::new(&session, cache_dir.join("submissions.txt"))?;
+    let reply = submitter.submit(day, part, &answer, SystemTime::now())?;
+    println!(
+        "day {} part {}: `{}` is {}",
+        day,
+        part,
+        answer.trim(),
+        reply.verdict
+    );
+    if !reply.wait.is_zero() {
+        println!("wait {}s before submitting again", reply.wait.as_secs());
+    }
+    if reply.verdict != Verdict::Correct {
+        return Err(format!("`{}` was not accepted", answer.trim()).into());
+    }
+    Ok(())
+}
+
+fn generate(
+    day: u8,
+    seed: Option<u64>,
+    size: usize,
+    output: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let generator = aoc::generator(day).ok_or_else(|| format!("no generator for day {}", day))?;
+    let seed = seed.unwrap_or_else(|| {
+        let seed = rand::random();
+        eprintln!("seed: {}", seed);
+        seed
+    });
+    let input = generator.generate_seeded(seed, size);
+    match output {
+        Some(path) => {
+            fs::write(&path, input).map_err(|err| format!("{}: {}", path.display(), err))?
+        }
+        None => io::stdout().lock().write_all(input.as_bytes())?,
+    }
+    Ok(())
+}
+
+fn verify(days: Vec<u8>, registry: Input) -> Result<(), Box<dyn Error>> {
+    let answers = verify::read_answers(&registry)?
+        .into_iter()
+        .filter(|answer| days.is_empty() || days.contains(&answer.day))
+        .collect::<Vec<_>>();
+    let checks = verify::verify(&answers, &verify::base_dir(&registry));
+    verify::print_report(io::stdout().lock(), &checks)?;
+
+    let failed = checks
+        .iter()
+        .filter(|check| check.outcome != Outcome::Pass)
+        .count();
+    if failed > 0 {
+        return Err(format!("{} of {} answers did not verify", failed, checks.len()).into());
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            lenient,
+            force,
+            format,
+            trace,
+            trace_file,
+            params,
+            config,
+        } => {
+            let mode = if lenient {
+                ParseMode::Lenient
+            } else {
+                ParseMode::Strict
+            };
+            read_config(config)
+                .and_then(|mut config| {
+                    for (name, value) in &params {
+                        config.set(day, name, value);
+                    }
+                    config.params(day)
+                })
+                .and_then(|params| {
+                    install_trace(trace, trace_file)
+                        .and_then(|_guard| run(day, part, input, mode, force, format, &params))
+                })
+        }
+        Command::Bench {
+            day,
+            iterations,
+            baseline,
+            save_baseline,
+        } => bench(day, iterations as usize, baseline, save_baseline),
+        Command::All {
+            day,
+            parallel,
+            format,
+            config,
+        } => all(day, parallel, format, config),
+        Command::Params { day } => params(day),
+        Command::Fetch {
+            day,
+            session,
+            cache_dir,
+        } => fetch(day, session, cache_dir),
+        Command::Submit {
+            day,
+            part,
+            answer,
+            input,
+            params,
+            config,
+            session,
+            cache_dir,
+        } => read_config(config).and_then(|mut config| {
+            for (name, value) in &params {
+                config.set(day, name, value);
+            }
+            let part = if part == 1 { Part::One } else { Part::Two };
+            submit(day, part, answer, input, &config, session, cache_dir)
+        }),
+        Command::Generate {
+            day,
+            seed,
+            size,
+            output,
+        } => generate(day, seed, size, output),
+        Command::Repl {
+            day,
+            part,
+            input,
+            params,
+            config,
+        } => read_config(config).and_then(|mut config| {
+            for (name, value) in &params {
+                config.set(day, name, value);
+            }
+            let part = if part == 1 { Part::One } else { Part::Two };
+            repl(day, part, input, &config.params(day)?)
+        }),
+        Command::Simulate {
+            day,
+            part,
+            input,
+            params,
+            config,
+            resume,
+            snapshot_every,
+            snapshot_dir,
+            until_step,
+        } => read_config(config).and_then(|mut config| {
+            for (name, value) in &params {
+                config.set(day, name, value);
+            }
+            let part = if part == 1 { Part::One } else { Part::Two };
+            simulate(
+                day,
+                part,
+                input,
+                &config.params(day)?,
+                resume,
+                snapshot_every.map(|every| (every as usize, snapshot_dir)),
+                until_step.map(|steps| steps as usize),
+            )
+        }),
+        Command::Serve { address } => server::serve(&address),
+        Command::Verify { day, answers } => verify(day, answers),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Rendering of solver results for people and for other tools.
+
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use aoc2022::{AnswerKind, Part};
+use serde::Serialize;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Human readable lines, like `day 1 part 1: 24000`.
+    #[default]
+    Text,
+    /// A JSON array with one object per answer.
+    Json,
+    /// CSV with a header row and one row per answer.
+    Csv,
+}
+
+/// The answer to one part of a day, along with how long it took to get it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub kind: AnswerKind,
+    /// Time spent parsing the input, shared by both parts of a day.
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// Why the part could not be solved, in which case the answer is empty.
+    pub error: Option<String>,
+}
+
+/// The JSON schema of a [`Record`].
+#[derive(Serialize)]
+pub(crate) struct JsonRecord<'a> {
+    day: u8,
+    part: u8,
+    answer: &'a str,
+    #[serde(rename = "type")]
+    kind: String,
+    parse_ns: u64,
+    solve_ns: u64,
+    error: Option<&'a str>,
+}
+
+impl<'a> From<&'a Record> for JsonRecord<'a> {
+    fn from(record: &'a Record) -> Self {
+        Self {
+            day: record.day,
+            part: match record.part {
+                Part::One => 1,
+                Part::Two => 2,
+            },
+            answer: &record.answer,
+            kind: record.kind.to_string(),
+            parse_ns: record.parse_time.as_nanos() as u64,
+            solve_ns: record.solve_time.as_nanos() as u64,
+            error: record.error.as_deref(),
+        }
+    }
+}
+
+/// Quotes a CSV field if it contains separators, quotes or line breaks.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Writes `records` in the given format.
+///
+/// The JSON and CSV schemas are stable: `day`, `part`, `answer`, `type`, `parse_ns`,
+/// `solve_ns` and `error`, where `type` is one of `integer`, `text` or `grid`. Answers are
+/// always strings, so that large numbers and multi-line grids survive unchanged. `error` is
+/// null, or empty in CSV, unless the part failed.
+pub fn write_records<W>(mut writer: W, format: Format, records: &[Record]) -> io::Result<()>
+where
+    W: Write,
+{
+    match format {
+        Format::Text => {
+            for record in records {
+                if let Some(error) = &record.error {
+                    writeln!(
+                        writer,
+                        "day {} part {} failed: {}",
+                        record.day, record.part, error
+                    )?;
+                } else if record.answer.contains('\n') {
+                    writeln!(
+                        writer,
+                        "day {} part {}:\n{}",
+                        record.day, record.part, record.answer
+                    )?;
+                } else {
+                    writeln!(
+                        writer,
+                        "day {} part {}: {}",
+                        record.day, record.part, record.answer
+                    )?;
+                }
+            }
+        }
+        Format::Json => {
+            let records = records.iter().map(JsonRecord::from).collect::<Vec<_>>();
+            serde_json::to_writer_pretty(&mut writer, &records)?;
+            writeln!(writer)?;
+        }
+        Format::Csv => {
+            writeln!(writer, "day,part,answer,type,parse_ns,solve_ns,error")?;
+            for record in records {
+                writeln!(
+                    writer,
+                    "{},{},{},{},{},{},{}",
+                    record.day,
+                    record.part,
+                    csv_field(&record.answer),
+                    record.kind,
+                    record.parse_time.as_nanos(),
+                    record.solve_time.as_nanos(),
+                    csv_field(record.error.as_deref().unwrap_or_default())
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 5,
+                part: Part::One,
+                answer: "CMZ".to_string(),
+                kind: AnswerKind::Text,
+                parse_time: Duration::from_nanos(1500),
+                solve_time: Duration::from_nanos(20),
+                error: None,
+            },
+            Record {
+                day: 10,
+                part: Part::Two,
+                answer: "#.\n.#".to_string(),
+                kind: AnswerKind::Grid,
+                parse_time: Duration::from_nanos(700),
+                solve_time: Duration::from_nanos(30),
+                error: None,
+            },
+            Record {
+                day: 7,
+                part: Part::Two,
+                answer: String::new(),
+                kind: AnswerKind::Integer,
+                parse_time: Duration::ZERO,
+                solve_time: Duration::ZERO,
+                error: Some("there is enough free space, already".to_string()),
+            },
+        ]
+    }
+
+    fn render(format: Format) -> String {
+        let mut output = vec![];
+        write_records(&mut output, format, &records()).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn renders_text() {
+        assert_eq!(
+            render(Format::Text),
+            "day 5 part 1: CMZ\nday 10 part 2:\n#.\n.#\n\
+             day 7 part 2 failed: there is enough free space, already\n"
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(Format::Json);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value[1],
+            serde_json::json!({
+                "day": 10,
+                "part": 2,
+                "answer": "#.\n.#",
+                "type": "grid",
+                "parse_ns": 700,
+                "solve_ns": 30,
+                "error": null,
+            })
+        );
+        assert_eq!(value[2]["error"], "there is enough free space, already");
+        let keys = [
+            "day", "part", "answer", "type", "parse_ns", "solve_ns", "error",
+        ];
+        let offsets = keys.map(|key| json.find(&format!("\"{}\"", key)).unwrap());
+        assert!(offsets.is_sorted(), "fields out of order: {}", json);
+    }
+
+    #[test]
+    fn renders_csv() {
+        assert_eq!(
+            render(Format::Csv),
+            "day,part,answer,type,parse_ns,solve_ns,error\n\
+             5,1,CMZ,text,1500,20,\n\
+             10,2,\"#.\n.#\",grid,700,30,\n\
+             7,2,,integer,0,0,\"there is enough free space, already\"\n"
+        );
+    }
+}
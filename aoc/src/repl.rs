@@ -0,0 +1,443 @@
+//! Following the simulations of some days step by step, to see where they go wrong.
+//!
+//! Commands:
+//!
+//! - `step [N]` advances by one or `N` steps and shows the state.
+//! - `run [until NAME OP NUMBER]` advances until the simulation is over or the condition
+//!   holds, like `run until cycle >= 20`.
+//! - `back [N]` goes back by one or `N` steps.
+//! - `show` shows the state, `values` the names and values conditions can use.
+//! - `save PATH` writes a [`Snapshot`] of the state, `load PATH` resumes from one.
+
+use std::{
+    error::Error,
+    io::{BufRead, Write},
+};
+
+use aoc2022::{Diagnostics, Input, Params, Part, Simulate, Stepwise};
+
+use crate::snapshot::{part_number, Snapshot};
+
+/// Steps between the saved states [`Repl::back`] replays from.
+const CHECKPOINT_INTERVAL: usize = 100;
+
+/// Steps `run` takes at most, so that a condition that never holds does not hang.
+const MAX_RUN_STEPS: usize = 1_000_000;
+
+const HELP: &str = "\
+step [N]                    advance by one or N steps
+run [until NAME OP NUMBER]  advance until the end or until the condition holds, like
+                            `run until cycle >= 20`; OP is one of == != < <= > >=
+back [N]                    go back by one or N steps
+show                        show the state
+save PATH                   write a snapshot of the state
+load PATH                   resume from a snapshot
+values                      list the values conditions can use
+help                        show this help
+quit                        leave";
+
+/// A condition on a value of the state, like `cycle >= 20`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Condition {
+    name: String,
+    op: String,
+    value: i64,
+}
+
+impl Condition {
+    fn parse(words: &[&str]) -> Result<Self, String> {
+        let [name, op, value] = words else {
+            return Err("expected a condition like `cycle >= 20`".to_string());
+        };
+        if !["==", "!=", "<", "<=", ">", ">="].contains(op) {
+            return Err(format!(
+                "unknown operator `{}`, expected one of == != < <= > >=",
+                op
+            ));
+        }
+        let value = value
+            .parse()
+            .map_err(|err| format!("`{}` is not a number: {}", value, err))?;
+        Ok(Self {
+            name: name.to_string(),
+            op: op.to_string(),
+            value,
+        })
+    }
+
+    fn holds(&self, value: i64) -> bool {
+        match self.op.as_str() {
+            "==" => value == self.value,
+            "!=" => value != self.value,
+            "<" => value < self.value,
+            "<=" => value <= self.value,
+            ">" => value > self.value,
+            _ => value >= self.value,
+        }
+    }
+}
+
+/// A simulation being followed, with enough of its history to go back.
+pub struct Repl<S> {
+    day: u8,
+    part: Part,
+    state: S,
+    steps: usize,
+    /// The states after every [`CHECKPOINT_INTERVAL`] steps, starting with the one after
+    /// `origin` steps, the first or a restored one.
+    checkpoints: Vec<S>,
+    origin: usize,
+}
+
+impl<S> Repl<S>
+where
+    S: Stepwise,
+{
+    pub fn new(day: u8, part: Part, state: S) -> Self {
+        Self {
+            day,
+            part,
+            checkpoints: vec![state.clone()],
+            state,
+            steps: 0,
+            origin: 0,
+        }
+    }
+
+    /// Advances by a step, or returns false if the simulation is over.
+    fn step(&mut self) -> bool {
+        if self.state.is_done() {
+            return false;
+        }
+        self.state.step();
+        self.steps += 1;
+        if (self.steps - self.origin).is_multiple_of(CHECKPOINT_INTERVAL) {
+            self.checkpoints.push(self.state.clone());
+        }
+        true
+    }
+
+    /// Goes back by `steps` steps, replaying from the last checkpoint before them, but not
+    /// beyond a restored snapshot.
+    fn back(&mut self, steps: usize) {
+        let target = self.steps.saturating_sub(steps).max(self.origin);
+        self.checkpoints
+            .truncate((target - self.origin) / CHECKPOINT_INTERVAL + 1);
+        self.state = self.checkpoints.last().unwrap().clone();
+        self.steps = self.origin + (self.checkpoints.len() - 1) * CHECKPOINT_INTERVAL;
+        while self.steps < target {
+            self.step();
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            day: self.day,
+            part: part_number(self.part),
+            step: self.steps,
+            state: serde_json::to_value(self.state.snapshot()).expect("states serialize to JSON"),
+        }
+    }
+
+    /// Resumes from `snapshot`, forgetting the steps before it.
+    fn restore(&mut self, snapshot: Snapshot) -> Result<(), Box<dyn Error>> {
+        if (snapshot.day, snapshot.part) != (self.day, part_number(self.part)) {
+            return Err(format!(
+                "the snapshot is of day {} part {}, not of day {} part {}",
+                snapshot.day, snapshot.part, self.day, self.part
+            )
+            .into());
+        }
+        let mut state = self.state.clone();
+        state.restore(serde_json::from_value(snapshot.state)?)?;
+        self.checkpoints = vec![state.clone()];
+        self.state = state;
+        self.steps = snapshot.step;
+        self.origin = snapshot.step;
+        Ok(())
+    }
+
+    /// The values of the state, along with the number of steps taken.
+    fn values(&self) -> Vec<(String, i64)> {
+        let mut values = vec![("step".to_string(), self.steps as i64)];
+        values.extend(self.state.values());
+        values
+    }
+
+    fn value(&self, name: &str) -> Result<i64, String> {
+        let values = self.values();
+        values
+            .iter()
+            .find(|(value, _)| value == name)
+            .map(|&(_, value)| value)
+            .ok_or_else(|| {
+                let names = values.into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+                format!("no value `{}`, expected one of {}", name, names.join(", "))
+            })
+    }
+
+    fn show(&self) -> String {
+        let status = if self.state.is_done() {
+            ", the simulation is over"
+        } else {
+            ""
+        };
+        format!("step {}{}\n{}", self.steps, status, self.state)
+    }
+
+    /// Executes a command and returns what to print.
+    pub fn execute(&mut self, command: &str) -> Result<String, String> {
+        let words = command.split_whitespace().collect::<Vec<_>>();
+        let count = |words: &[&str]| match words {
+            [] => Ok(1),
+            [count] => count
+                .parse::<usize>()
+                .map_err(|err| format!("`{}` is not a number of steps: {}", count, err)),
+            _ => Err(format!("unexpected `{}`", words[1..].join(" "))),
+        };
+        match words.as_slice() {
+            ["step", rest @ ..] => {
+                for _ in 0..count(rest)? {
+                    if !self.step() {
+                        break;
+                    }
+                }
+                Ok(self.show())
+            }
+            ["run"] => {
+                for _ in 0..MAX_RUN_STEPS {
+                    if !self.step() {
+                        return Ok(self.show());
+                    }
+                }
+                if self.state.is_done() {
+                    return Ok(self.show());
+                }
+                Err(format!(
+                    "stopped after {} steps, the simulation is not over\n{}",
+                    MAX_RUN_STEPS,
+                    self.show()
+                ))
+            }
+            ["run", "until", condition @ ..] => {
+                let condition = Condition::parse(condition)?;
+                self.value(&condition.name)?;
+                for _ in 0..MAX_RUN_STEPS {
+                    if !self.step() || condition.holds(self.value(&condition.name)?) {
+                        return Ok(self.show());
+                    }
+                }
+                Err(format!(
+                    "the condition did not hold within {} steps\n{}",
+                    MAX_RUN_STEPS,
+                    self.show()
+                ))
+            }
+            ["back", rest @ ..] => {
+                self.back(count(rest)?);
+                Ok(self.show())
+            }
+            ["show"] => Ok(self.show()),
+            ["save", path] => {
+                self.snapshot().write(path).map_err(|err| err.to_string())?;
+                Ok(format!("saved step {} to {}", self.steps, path))
+            }
+            ["load", path] => {
+                Snapshot::read(path)
+                    .and_then(|snapshot| self.restore(snapshot))
+                    .map_err(|err| err.to_string())?;
+                Ok(self.show())
+            }
+            ["values"] => Ok(self
+                .values()
+                .iter()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ["help"] => Ok(HELP.to_string()),
+            [] => Ok(String::new()),
+            [command, ..] => Err(format!("unknown command `{}`, try `help`", command)),
+        }
+    }
+}
+
+/// A [`Repl`] of any day.
+pub trait Session {
+    /// Executes a command and returns what to print.
+    fn execute(&mut self, command: &str) -> Result<String, String>;
+
+    /// Advances by a step, or returns false if the simulation is over.
+    fn step(&mut self) -> bool;
+
+    /// The number of steps taken, including those before a restored snapshot.
+    fn steps(&self) -> usize;
+
+    fn snapshot(&self) -> Snapshot;
+
+    /// Resumes from a snapshot of the same day and part.
+    fn restore(&mut self, snapshot: Snapshot) -> Result<(), Box<dyn Error>>;
+}
+
+impl<S> Session for Repl<S>
+where
+    S: Stepwise,
+{
+    fn execute(&mut self, command: &str) -> Result<String, String> {
+        Repl::execute(self, command)
+    }
+
+    fn step(&mut self) -> bool {
+        Repl::step(self)
+    }
+
+    fn steps(&self) -> usize {
+        self.steps
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Repl::snapshot(self)
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) -> Result<(), Box<dyn Error>> {
+        Repl::restore(self, snapshot)
+    }
+}
+
+fn start<S>(
+    day: u8,
+    input: &Input,
+    part: Part,
+    params: &Params,
+) -> Result<Box<dyn Session>, Box<dyn Error>>
+where
+    S: Simulate,
+    S::State: 'static,
+{
+    let input = S::parse_input_with(input.clone(), &mut Diagnostics::default())?;
+    Ok(Box::new(Repl::new(
+        day,
+        part,
+        S::start(&input, part, params)?,
+    )))
+}
+
+/// Starts following the simulation of `part` of `day` on `input`.
+pub fn session(
+    day: u8,
+    input: &Input,
+    part: Part,
+    params: &Params,
+) -> Result<Box<dyn Session>, Box<dyn Error>> {
+    match day {
+        5 => start::<day05::Day05>(day, input, part, params),
+        9 => start::<day09::Day09>(day, input, part, params),
+        10 => start::<day10::Day10>(day, input, part, params),
+        11 => start::<day11::Day11>(day, input, part, params),
+        _ => Err(format!(
+            "day {} cannot be followed step by step, expected 5, 9, 10 or 11",
+            day
+        )
+        .into()),
+    }
+}
+
+/// Reads commands from `reader` and writes their output to `writer` until `quit` or the
+/// end of the commands.
+pub fn run<R, W>(session: &mut dyn Session, reader: R, mut writer: W) -> Result<(), Box<dyn Error>>
+where
+    R: BufRead,
+    W: Write,
+{
+    writeln!(writer, "{}", session.execute("show")?)?;
+    let mut lines = reader.lines();
+    loop {
+        write!(writer, "> ")?;
+        writer.flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            writeln!(writer)?;
+            return Ok(());
+        };
+        match line.trim() {
+            "quit" | "exit" => return Ok(()),
+            command => match session.execute(command) {
+                Ok(output) if output.is_empty() => {}
+                Ok(output) => writeln!(writer, "{}", output)?,
+                Err(err) => writeln!(writer, "error: {}", err)?,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(day: u8, path: &str, part: Part) -> Box<dyn Session> {
+        super::session(day, &Input::path(path), part, &Params::default()).unwrap()
+    }
+
+    #[test]
+    fn steps_can_be_taken_back() {
+        let mut repl = session(9, "../day09/input_test.txt", Part::One);
+        let after_250 = repl.execute("step 250").unwrap();
+        assert!(after_250.starts_with("step 24, the simulation is over\n"));
+        assert!(repl.execute("values").unwrap().contains("visited = 13"));
+        repl.execute("back 20").unwrap();
+        assert!(repl.execute("show").unwrap().starts_with("step 4\n"));
+        assert_eq!(repl.execute("step 20").unwrap(), after_250);
+        assert!(repl.execute("back 100").unwrap().starts_with("step 0\n"));
+    }
+
+    #[test]
+    fn runs_stop_when_conditions_hold() {
+        let mut repl = session(10, "../day10/input_test2.txt", Part::One);
+        let shown = repl.execute("run until cycle >= 220").unwrap();
+        assert!(shown.contains("during cycle 220: x = 18, signal strength 3960"));
+        assert!(repl
+            .execute("run")
+            .unwrap()
+            .contains(", the simulation is over"));
+        assert_eq!(
+            repl.execute("run until cycles > 1").unwrap_err(),
+            "no value `cycles`, expected one of step, cycle, x, signal"
+        );
+        assert!(repl.execute("run until x =< 1").is_err());
+        assert!(repl.execute("jump").is_err());
+    }
+
+    #[test]
+    fn long_runs_are_cut_short() {
+        let mut params = Params::default();
+        let parameters = crate::solver(11).unwrap().parameters();
+        params
+            .set(parameters, "rounds_without_relief", "2000000")
+            .unwrap();
+        let mut repl = super::session(
+            11,
+            &Input::path("../day11/test_input.txt"),
+            Part::Two,
+            &params,
+        )
+        .unwrap();
+        let err = repl.execute("run").unwrap_err();
+        assert!(err.starts_with("stopped after 1000000 steps, the simulation is not over"));
+        assert_eq!(repl.steps(), MAX_RUN_STEPS);
+    }
+
+    #[test]
+    fn commands_are_read_until_quit() {
+        let mut repl = session(11, "../day11/test_input.txt", Part::One);
+        let mut output = vec![];
+        run(
+            repl.as_mut(),
+            "step 20\nshow\nquit\nstep\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("step 20, the simulation is over\nafter round 20 of 20:"));
+        assert!(output.contains("monkey business: 10605"));
+        assert_eq!(output.matches("> ").count(), 3);
+        assert!(super::session(1, &Input::text(""), Part::One, &Params::default()).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+//! A local HTTP API, so that tools in other languages can call the solutions.
+//!
+//! - `GET /days` lists the days with the answer types of their parts and their parameters.
+//! - `POST /days/{n}/parts/{p}` solves a part of a day, with the puzzle input as the request
+//!   body and parameters in the query string, like `/days/11/parts/1?rounds=30`.
+//!
+//! Answers are returned in the JSON schema of `aoc run --format json`, errors as
+//! `{"error": "..."}`.
+
+use std::{
+    error::Error,
+    panic::{self, AssertUnwindSafe},
+    thread,
+    time::Instant,
+};
+
+use aoc2022::{Diagnostics, Input, Params, Part};
+use serde::Serialize;
+use serde_json::json;
+use tiny_http::{Header, Server};
+
+use crate::{
+    all::panic_message,
+    output::{JsonRecord, Record},
+    solver,
+};
+
+/// Status code and JSON body of a response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn json<T>(status: u16, value: &T) -> Self
+    where
+        T: Serialize,
+    {
+        Self {
+            status,
+            body: serde_json::to_string(value).expect("responses serialize to JSON"),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::json(status, &json!({ "error": message.into() }))
+    }
+}
+
+/// Decodes a `%XX` and `+` encoded component of a query string.
+fn decode(component: &str) -> Result<String, String> {
+    let mut bytes = vec![];
+    let mut rest = component.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        bytes.push(match byte {
+            b'+' => b' ',
+            b'%' => {
+                let hex = rest
+                    .get(..2)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("invalid escape in `{}`", component))?;
+                rest = &rest[2..];
+                hex
+            }
+            byte => byte,
+        });
+    }
+    String::from_utf8(bytes).map_err(|_| format!("`{}` is not UTF-8", component))
+}
+
+fn list_days() -> Response {
+    let days = crate::DAYS
+        .filter_map(|day| Some((day, solver(day)?)))
+        .map(|(day, solver)| {
+            let parts = [(1, Part::One), (2, Part::Two)].map(|(number, part)| {
+                json!({ "part": number, "type": solver.answer_kind(part).to_string() })
+            });
+            let parameters = solver
+                .parameters()
+                .iter()
+                .map(|parameter| {
+                    json!({
+                        "name": parameter.name(),
+                        "default": parameter.default_value(),
+                        "description": parameter.description(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({ "day": day, "parts": parts, "parameters": parameters })
+        })
+        .collect::<Vec<_>>();
+    Response::json(200, &days)
+}
+
+fn solve(day: &str, part: &str, query: &str, body: &str) -> Response {
+    let Some((day, solver)) = day.parse().ok().and_then(|day| Some((day, solver(day)?))) else {
+        return Response::error(404, format!("no solution for day `{}`", day));
+    };
+    let part = match part {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => return Response::error(404, format!("no part `{}`, expected 1 or 2", part)),
+    };
+
+    let mut params = Params::default();
+    for assignment in query.split('&').filter(|assignment| !assignment.is_empty()) {
+        let (name, value) = assignment.split_once('=').unwrap_or((assignment, ""));
+        let result = decode(name)
+            .and_then(|name| Ok((name, decode(value)?)))
+            .and_then(|(name, value)| params.set(solver.parameters(), &name, &value));
+        if let Err(err) = result {
+            return Response::error(400, err);
+        }
+    }
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let start = Instant::now();
+        let input = solver.parse_input(&Input::text(body), &mut Diagnostics::default())?;
+        let parse_time = start.elapsed();
+        let start = Instant::now();
+        let answer = solver.solve(input.as_ref(), part, &params)?;
+        Ok::<_, Box<dyn Error>>(Record {
+            day,
+            part,
+            answer,
+            kind: solver.answer_kind(part),
+            parse_time,
+            solve_time: start.elapsed(),
+            error: None,
+        })
+    }));
+    match result {
+        Ok(Ok(record)) => Response::json(200, &JsonRecord::from(&record)),
+        Ok(Err(err)) => Response::error(422, err.to_string()),
+        Err(payload) => Response::error(500, panic_message(payload)),
+    }
+}
+
+/// Routes a request to its endpoint.
+pub fn handle(method: &str, url: &str, body: &str) -> Response {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    match (method, segments.as_slice()) {
+        ("GET", ["days"]) => list_days(),
+        ("POST", ["days", day, "parts", part]) => solve(day, part, query, body),
+        (_, ["days"] | ["days", _, "parts", _]) => {
+            Response::error(405, format!("method {} is not allowed", method))
+        }
+        _ => Response::error(404, format!("no endpoint at `{}`", path)),
+    }
+}
+
+/// Answers requests on `server` until it fails, each on its own thread.
+pub fn run(server: Server) -> Result<(), Box<dyn Error>> {
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    loop {
+        let mut request = server.recv()?;
+        let content_type = content_type.clone();
+        thread::spawn(move || {
+            let mut body = String::new();
+            let response = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => handle(&request.method().to_string(), request.url(), &body),
+                Err(err) => Response::error(400, format!("unreadable body: {}", err)),
+            };
+            let response = tiny_http::Response::from_string(response.body)
+                .with_status_code(response.status)
+                .with_header(content_type);
+            // The client may have gone away, which only concerns that client.
+            let _ = request.respond(response);
+        });
+    }
+}
+
+/// Listens on `address`, like `127.0.0.1:8022`, and answers requests until it fails.
+pub fn serve(address: &str) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(address).map_err(|err| format!("{}: {}", address, err))?;
+    eprintln!("listening on http://{}", server.server_addr());
+    run(server)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::{Shutdown, TcpStream},
+    };
+
+    use serde_json::Value;
+
+    use super::*;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    fn body(response: &Response) -> Value {
+        serde_json::from_str(&response.body).unwrap()
+    }
+
+    #[test]
+    fn parts_are_solved_with_parameters() {
+        let response = handle("POST", "/days/1/parts/2", EXAMPLE);
+        assert_eq!(response.status, 200);
+        assert_eq!(body(&response)["answer"], "45000");
+        assert_eq!(body(&response)["type"], "integer");
+
+        let response = handle("POST", "/days/1/parts/2?top=2", EXAMPLE);
+        assert_eq!(body(&response)["answer"], "35000");
+        let response = handle("POST", "/days/10/parts/1?signal_cycles=20%2C60", "noop\n");
+        assert_eq!(response.status, 200, "{}", response.body);
+    }
+
+    #[test]
+    fn errors_have_status_codes() {
+        let error = |method, url, input| {
+            let response = handle(method, url, input);
+            (response.status, body(&response)["error"].clone())
+        };
+        assert_eq!(error("POST", "/days/1/parts/2?top=x", EXAMPLE).0, 400);
+        assert_eq!(error("POST", "/days/1/parts/2?rounds=1", EXAMPLE).0, 400);
+        assert_eq!(error("POST", "/days/12/parts/1", EXAMPLE).0, 404);
+        assert_eq!(error("POST", "/days/1/parts/3", EXAMPLE).0, 404);
+        assert_eq!(error("GET", "/days/1/parts/1", EXAMPLE).0, 405);
+        assert_eq!(error("GET", "/", "").0, 404);
+        let (status, message) = error("POST", "/days/2/parts/1", "A Q\n");
+        assert_eq!(status, 422);
+        assert!(message.as_str().unwrap().contains("found `Q`"));
+    }
+
+    #[test]
+    fn days_are_listed_with_parameters() {
+        let response = handle("GET", "/days", "");
+        let days = body(&response);
+        assert_eq!(days.as_array().unwrap().len(), crate::DAYS.count());
+        assert_eq!(days[9]["parts"][1]["type"], "grid");
+        assert_eq!(days[10]["parameters"][0]["name"], "rounds");
+        assert_eq!(days[10]["parameters"][0]["default"], "20");
+    }
+
+    #[test]
+    fn server_answers_over_http() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let address = server.server_addr().to_ip().unwrap();
+        thread::spawn(move || run(server).map_err(|err| err.to_string()));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "POST /days/1/parts/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            EXAMPLE.len(),
+            EXAMPLE
+        )
+        .unwrap();
+        stream.shutdown(Shutdown::Write).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("application/json"));
+        assert!(response.contains(r#""answer":"24000""#), "{}", response);
+    }
+}
@@ -0,0 +1,154 @@
+//! Snapshots of the simulations of [`crate::repl`], to checkpoint long runs and to attach
+//! exact states to bug reports.
+//!
+//! A snapshot is a JSON file with the day, part and step it was taken at along with the
+//! state, like `{"day": 11, "part": 2, "step": 1000, "state": {...}}`. The state only holds
+//! what changes while stepping, so a snapshot is resumed on the input it was taken of.
+
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc2022::Part;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::repl::Session;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub day: u8,
+    pub part: u8,
+    pub step: usize,
+    pub state: Value,
+}
+
+/// The number of `part` in snapshots.
+pub fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+impl Snapshot {
+    pub fn read<P>(path: P) -> Result<Self, Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let text =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Ok(serde_json::from_str(&text).map_err(|err| format!("{}: {}", path.display(), err))?)
+    }
+
+    pub fn write<P>(&self, path: P) -> Result<(), Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self).expect("snapshots serialize to JSON");
+        fs::write(path, json + "\n").map_err(|err| format!("{}: {}", path.display(), err))?;
+        Ok(())
+    }
+
+    /// The name of the file [`simulate`] writes the snapshot to, like
+    /// `day11-part2-step00001000.json`, so that the snapshots of a run sort by step.
+    pub fn file_name(&self) -> String {
+        format!(
+            "day{:02}-part{}-step{:08}.json",
+            self.day, self.part, self.step
+        )
+    }
+}
+
+/// Runs `session` until the simulation is over or `until` steps were taken, writing a
+/// snapshot to `dir` every `every` steps, and returns the paths written.
+pub fn simulate(
+    session: &mut dyn Session,
+    every: Option<usize>,
+    dir: &Path,
+    until: Option<usize>,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    if every.is_some() {
+        fs::create_dir_all(dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+    }
+    let mut written = vec![];
+    while until.is_none_or(|until| session.steps() < until) && session.step() {
+        if every.is_some_and(|every| session.steps().is_multiple_of(every)) {
+            let snapshot = session.snapshot();
+            let path = dir.join(snapshot.file_name());
+            snapshot.write(&path)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use aoc2022::{Input, Params};
+
+    use super::*;
+    use crate::repl::session;
+
+    fn game(part: Part) -> Box<dyn Session> {
+        let input = Input::path("../day11/test_input.txt");
+        session(11, &input, part, &Params::default()).unwrap()
+    }
+
+    #[test]
+    fn runs_resume_from_snapshots() {
+        let dir = env::temp_dir().join(format!("aoc-snapshot-{}", std::process::id()));
+        let mut session = game(Part::Two);
+        let written = simulate(session.as_mut(), Some(2500), &dir, None).unwrap();
+        assert_eq!(written.len(), 4);
+        assert!(written[1].ends_with("day11-part2-step00005000.json"));
+        let end = session.execute("show").unwrap();
+        assert!(end.contains("monkey business: 2713310158"));
+
+        let mut resumed = game(Part::Two);
+        resumed
+            .restore(Snapshot::read(&written[1]).unwrap())
+            .unwrap();
+        assert_eq!(resumed.steps(), 5000);
+        assert_eq!(resumed.snapshot(), Snapshot::read(&written[1]).unwrap());
+        assert!(simulate(resumed.as_mut(), None, &dir, None)
+            .unwrap()
+            .is_empty());
+        assert_eq!(resumed.execute("show").unwrap(), end);
+        assert!(resumed
+            .execute("back 6000")
+            .unwrap()
+            .starts_with("step 5000\n"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn snapshots_must_fit_the_simulation() {
+        let mut session = game(Part::One);
+        simulate(session.as_mut(), None, Path::new("."), Some(5)).unwrap();
+        let snapshot = session.snapshot();
+        assert_eq!(snapshot.step, 5);
+
+        let err = game(Part::Two).restore(snapshot.clone()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the snapshot is of day 11 part 1, not of day 11 part 2"
+        );
+        let mut fewer = snapshot.clone();
+        fewer.state["inspections"].as_array_mut().unwrap().pop();
+        assert!(game(Part::One).restore(fewer).is_err());
+        let mut later = snapshot;
+        later.state["round"] = 21.into();
+        let err = game(Part::One).restore(later).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the snapshot is after round 21, but there are only 20"
+        );
+    }
+}
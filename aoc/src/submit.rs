@@ -0,0 +1,543 @@
+//! Submitting answers, without repeating guesses or ignoring the cooldown of the site.
+//!
+//! Every submission is appended to a log, so that an answer is never sent twice, a guess
+//! beyond a known too high or too low answer is refused, and no answer is sent before the
+//! site is ready to take the next one.
+
+use std::{
+    error::Error,
+    fmt,
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc2022::{
+    parser::{number, one_of, pair, parse_line, preceded, rest, tag},
+    Input, Part,
+};
+
+use crate::fetch::{Http, Ureq, BASE_URL};
+
+/// What the site made of an answer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// The previous answer was too recent, this one was not checked.
+    TooSoon,
+    /// The part is locked or solved already, the answer was not checked.
+    WrongLevel,
+}
+
+impl Verdict {
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("correct", Self::Correct),
+        ("too_high", Self::TooHigh),
+        ("too_low", Self::TooLow),
+        ("incorrect", Self::Incorrect),
+        ("too_soon", Self::TooSoon),
+        ("wrong_level", Self::WrongLevel),
+    ];
+
+    fn name(self) -> &'static str {
+        let (name, _) = Self::NAMES
+            .iter()
+            .find(|(_, verdict)| *verdict == self)
+            .unwrap();
+        name
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooSoon => "not checked, submitted too soon",
+            Verdict::WrongLevel => "not checked, the part is locked or solved already",
+        })
+    }
+}
+
+/// The verdict of the site, and how long it wants to be left alone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Reply {
+    pub verdict: Verdict,
+    pub wait: Duration,
+}
+
+/// Reads a duration like `one minute`, `5 minutes` or `1m 30s`.
+fn parse_duration(text: &str) -> Option<Duration> {
+    let mut seconds = 0;
+    let mut words = text.split_whitespace();
+    while let Some(word) = words.next() {
+        let digits = word
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(word.len());
+        let (count, unit) = match word.split_at(digits) {
+            ("", "one") => (1, words.next()?),
+            ("", _) => return None,
+            (count, "") => (count.parse().ok()?, words.next()?),
+            (count, unit) => (count.parse().ok()?, unit),
+        };
+        seconds += count
+            * match unit.trim_end_matches(['.', ',']) {
+                "h" | "hour" | "hours" => 3600,
+                "m" | "minute" | "minutes" => 60,
+                "s" | "second" | "seconds" => 1,
+                _ => return None,
+            };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// The text between `end` and the closest `start` before it, as a duration.
+fn duration_between(text: &str, start: &str, end: &str) -> Option<Duration> {
+    let (before, _) = text.split_once(end)?;
+    let (_, duration) = before.rsplit_once(start)?;
+    parse_duration(duration)
+}
+
+impl Reply {
+    /// Reads the verdict out of the HTML page the site answers a submission with.
+    pub fn parse(page: &str) -> Result<Self, String> {
+        let page = page.to_lowercase();
+        let reply = |verdict, wait: Option<Duration>| Self {
+            verdict,
+            wait: wait.unwrap_or_default(),
+        };
+        if page.contains("that's the right answer") {
+            Ok(reply(Verdict::Correct, None))
+        } else if page.contains("that's not the right answer") {
+            let verdict = if page.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if page.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            };
+            let wait = duration_between(&page, "please wait ", " before trying again");
+            Ok(reply(verdict, wait))
+        } else if page.contains("you gave an answer too recently") {
+            let wait = duration_between(&page, "you have ", " left to wait");
+            Ok(reply(Verdict::TooSoon, wait))
+        } else if page.contains("you don't seem to be solving the right level") {
+            Ok(reply(Verdict::WrongLevel, None))
+        } else {
+            Err("the site answered with a page that has no verdict".to_string())
+        }
+    }
+}
+
+/// An answer that was sent, and what came of it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: u8,
+    pub part: Part,
+    /// When the answer was sent, in seconds since the Unix epoch.
+    pub time: u64,
+    pub verdict: Verdict,
+    pub wait: Duration,
+    pub answer: String,
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// The submissions so far, stored one `day part time verdict wait answer` per line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Log(Vec<Submission>);
+
+impl Log {
+    /// Reads the log at `path`; a missing log has no submissions yet.
+    pub fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let input = Input::path(path);
+        let lines = match input.lines() {
+            Ok(lines) => lines,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(format!("{}: {}", input, err).into()),
+        };
+        let mut submissions = vec![];
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (day, (part, (time, (verdict, (wait, answer))))) = parse_line(
+                index + 1,
+                &line,
+                pair(
+                    number(),
+                    pair(
+                        preceded(tag(" "), one_of(&[("1", Part::One), ("2", Part::Two)])),
+                        pair(
+                            preceded(tag(" "), number()),
+                            pair(
+                                preceded(tag(" "), one_of(Verdict::NAMES)),
+                                pair(preceded(tag(" "), number()), preceded(tag(" "), rest())),
+                            ),
+                        ),
+                    ),
+                ),
+            )
+            .map_err(|err| err.in_file(input.to_string()))?;
+            submissions.push(Submission {
+                day,
+                part,
+                time,
+                verdict,
+                wait: Duration::from_secs(wait),
+                answer: answer.to_string(),
+            });
+        }
+        Ok(Self(submissions))
+    }
+
+    /// Appends `submission` to the log at `path`, creating it if needed.
+    fn append(&mut self, path: &Path, submission: Submission) -> Result<(), Box<dyn Error>> {
+        let in_file = |err| format!("{}: {}", path.display(), err);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(in_file)?;
+        if file.metadata().map_err(in_file)?.len() == 0 {
+            writeln!(file, "# day part time verdict wait_s answer").map_err(in_file)?;
+        }
+        let Submission {
+            day,
+            part,
+            time,
+            verdict,
+            wait,
+            answer,
+        } = &submission;
+        writeln!(
+            file,
+            "{} {} {} {} {} {}",
+            day,
+            part,
+            time,
+            verdict.name(),
+            wait.as_secs(),
+            answer
+        )
+        .map_err(in_file)?;
+        self.0.push(submission);
+        Ok(())
+    }
+
+    /// Checks that sending `answer` at `now` can tell us something new.
+    pub fn check(&self, day: u8, part: Part, answer: &str, now: SystemTime) -> Result<(), String> {
+        let puzzle = || {
+            self.0
+                .iter()
+                .filter(move |submission| submission.day == day && submission.part == part)
+        };
+        if let Some(solved) = puzzle().find(|submission| submission.verdict == Verdict::Correct) {
+            return Err(format!(
+                "day {} part {} is solved already, the answer was `{}`",
+                day, part, solved.answer
+            ));
+        }
+        if let Some(sent) = puzzle().find(|submission| {
+            submission.answer == answer && submission.verdict != Verdict::TooSoon
+        }) {
+            return Err(format!(
+                "`{}` was submitted for day {} part {} already, it was {}",
+                answer, day, part, sent.verdict
+            ));
+        }
+        if let Ok(value) = answer.parse::<i64>() {
+            for submission in puzzle() {
+                let Ok(bound) = submission.answer.parse::<i64>() else {
+                    continue;
+                };
+                let beyond = match submission.verdict {
+                    Verdict::TooHigh => value >= bound,
+                    Verdict::TooLow => value <= bound,
+                    _ => false,
+                };
+                if beyond {
+                    return Err(format!(
+                        "`{}` is {} as well, `{}` was {} already",
+                        answer, submission.verdict, bound, submission.verdict
+                    ));
+                }
+            }
+        }
+        let ready = self
+            .0
+            .iter()
+            .map(|submission| submission.time + submission.wait.as_secs())
+            .max()
+            .unwrap_or(0);
+        let now = unix_time(now);
+        if now < ready {
+            return Err(format!(
+                "the site wants a break, wait {}s before submitting again",
+                ready - now
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Sends answers to the site, keeping a [`Log`] of them.
+pub struct Submitter {
+    base_url: String,
+    session: String,
+    log_path: PathBuf,
+    log: Log,
+    http: Box<dyn Http>,
+}
+
+impl Submitter {
+    /// Submits with the `session` cookie, keeping the log at `log_path`.
+    pub fn new<P>(session: &str, log_path: P) -> Result<Self, Box<dyn Error>>
+    where
+        P: Into<PathBuf>,
+    {
+        let log_path = log_path.into();
+        Ok(Self {
+            base_url: BASE_URL.to_string(),
+            session: session.trim().to_string(),
+            log: Log::read(&log_path)?,
+            log_path,
+            http: Box::new(Ureq),
+        })
+    }
+
+    /// Submits to another site, like a local stand-in for it.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sends the requests with `http` instead of [`Ureq`].
+    pub fn with_http<H>(mut self, http: H) -> Self
+    where
+        H: Http + 'static,
+    {
+        self.http = Box::new(http);
+        self
+    }
+
+    /// Sends `answer` unless the log shows that doing so at `now` would be pointless, and
+    /// logs the reply.
+    pub fn submit(
+        &mut self,
+        day: u8,
+        part: Part,
+        answer: &str,
+        now: SystemTime,
+    ) -> Result<Reply, Box<dyn Error>> {
+        let answer = answer.trim();
+        if answer.is_empty() || answer.contains('\n') {
+            return Err(format!("`{}` is not an answer that can be submitted", answer).into());
+        }
+        if self.session.is_empty() {
+            return Err("a session token is needed to submit answers".into());
+        }
+        self.log.check(day, part, answer, now)?;
+
+        let url = format!("{}/2022/day/{}/answer", self.base_url, day);
+        let level = part.to_string();
+        let page = self.http.post(
+            &url,
+            &self.session,
+            &[("level", &level), ("answer", answer)],
+        )?;
+        let reply = Reply::parse(&page).map_err(|err| format!("{}: {}", url, err))?;
+        self.log.append(
+            &self.log_path,
+            Submission {
+                day,
+                part,
+                time: unix_time(now),
+                verdict: reply.verdict,
+                wait: reply.wait,
+                answer: answer.to_string(),
+            },
+        )?;
+        Ok(reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, sync::Mutex, thread};
+
+    use tiny_http::{Response, Server};
+
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    fn reply(verdict: Verdict, wait: u64) -> Reply {
+        Reply {
+            verdict,
+            wait: Duration::from_secs(wait),
+        }
+    }
+
+    #[test]
+    fn replies_are_parsed() {
+        let parse = |article| Reply::parse(&page(article)).unwrap();
+        assert_eq!(
+            parse("That's the right answer! You are one gold star closer."),
+            reply(Verdict::Correct, 0)
+        );
+        assert_eq!(
+            parse(
+                "That's not the right answer; your answer is too high. If you're stuck, \
+                 make sure you're using the full input data. Please wait one minute before \
+                 trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>"
+            ),
+            reply(Verdict::TooHigh, 60)
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too low. please wait 5 minutes before trying again."),
+            reply(Verdict::TooLow, 300)
+        );
+        assert_eq!(
+            parse("That's not the right answer. Please wait one minute before trying again."),
+            reply(Verdict::Incorrect, 60)
+        );
+        assert_eq!(
+            parse("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."),
+            reply(Verdict::TooSoon, 65)
+        );
+        assert_eq!(
+            parse("You don't seem to be solving the right level.  Did you already complete it?"),
+            reply(Verdict::WrongLevel, 0)
+        );
+        assert!(Reply::parse(&page("Puzzle inputs differ by user.")).is_err());
+    }
+
+    fn submission(part: Part, time: u64, verdict: Verdict, answer: &str) -> Submission {
+        Submission {
+            day: 1,
+            part,
+            time,
+            verdict,
+            wait: Duration::from_secs(60),
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn pointless_guesses_are_refused() {
+        let log = Log(vec![
+            submission(Part::One, 1000, Verdict::TooHigh, "500"),
+            submission(Part::One, 1100, Verdict::TooLow, "100"),
+            submission(Part::One, 1200, Verdict::TooSoon, "200"),
+            submission(Part::Two, 1300, Verdict::Correct, "42"),
+        ]);
+        let check = |part, answer, now| log.check(1, part, answer, at(now));
+        assert_eq!(check(Part::One, "300", 1400), Ok(()));
+        assert_eq!(check(Part::One, "200", 1400), Ok(()));
+        assert_eq!(check(Part::One, "abc", 1400), Ok(()));
+        assert_eq!(
+            check(Part::One, "600", 1400),
+            Err("`600` is too high as well, `500` was too high already".to_string())
+        );
+        assert!(check(Part::One, "100", 1400).is_err());
+        assert_eq!(
+            check(Part::Two, "43", 1400),
+            Err("day 1 part 2 is solved already, the answer was `42`".to_string())
+        );
+        assert_eq!(
+            check(Part::One, "300", 1330),
+            Err("the site wants a break, wait 30s before submitting again".to_string())
+        );
+        assert_eq!(log.check(2, Part::One, "500", at(1400)), Ok(()));
+    }
+
+    #[test]
+    fn answers_are_submitted_and_logged() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+        let log_path = env::temp_dir().join(format!("aoc-submit-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&log_path);
+        let forms = Mutex::new(vec![]);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for mut request in server.incoming_requests() {
+                    let mut form = String::new();
+                    request.as_reader().read_to_string(&mut form).unwrap();
+                    let article = match form.as_str() {
+                        "level=1&answer=24000" => "That's the right answer!",
+                        _ => "That's not the right answer; your answer is too low. Please wait one minute before trying again.",
+                    };
+                    forms.lock().unwrap().push((request.url().to_string(), form));
+                    request.respond(Response::from_string(page(article))).unwrap();
+                }
+            });
+
+            let mut submitter = Submitter::new("secret", &log_path)
+                .unwrap()
+                .with_base_url(&base_url);
+            assert_eq!(
+                submitter.submit(1, Part::One, "2400", at(1000)).unwrap(),
+                reply(Verdict::TooLow, 60)
+            );
+            let err = submitter
+                .submit(1, Part::One, "24000", at(1030))
+                .unwrap_err();
+            assert!(err.to_string().contains("wait 30s"));
+
+            // The log survives, so a new submitter knows about the earlier answers.
+            let mut submitter = Submitter::new("secret", &log_path)
+                .unwrap()
+                .with_base_url(&base_url);
+            assert!(submitter.submit(1, Part::One, "2400", at(1100)).is_err());
+            assert_eq!(
+                submitter.submit(1, Part::One, "24000\n", at(1100)).unwrap(),
+                reply(Verdict::Correct, 0)
+            );
+            server.unblock();
+        });
+
+        assert_eq!(
+            forms.into_inner().unwrap(),
+            [
+                (
+                    "/2022/day/1/answer".to_string(),
+                    "level=1&answer=2400".to_string()
+                ),
+                (
+                    "/2022/day/1/answer".to_string(),
+                    "level=1&answer=24000".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(&log_path).unwrap(),
+            "# day part time verdict wait_s answer\n\
+             1 1 1000 too_low 60 2400\n\
+             1 1 1100 correct 0 24000\n"
+        );
+        fs::remove_file(log_path).unwrap();
+    }
+}
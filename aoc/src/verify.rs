@@ -0,0 +1,297 @@
+//! Checking solutions against a registry of known answers.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use aoc2022::{
+    parser::{number, one_of, pair, parse_line, preceded, rest, tag, word},
+    Diagnostics, Input, Params, ParseError, ParseErrorKind, Part,
+};
+
+use crate::{all::panic_message, solver};
+
+/// The known answer to one part of a day, for a specific input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub answer: String,
+}
+
+const PARTS: &[(&str, Part)] = &[("1", Part::One), ("2", Part::Two)];
+
+/// Escapes line breaks and backslashes, so that multi-line answers fit on one line.
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Reverses [`escape`], failing with the offset of an invalid escape sequence.
+fn unescape(answer: &str) -> Result<String, usize> {
+    let mut result = String::with_capacity(answer.len());
+    let mut chars = answer.char_indices();
+    while let Some((offset, c)) = chars.next() {
+        result.push(match c {
+            '\\' => match chars.next() {
+                Some((_, '\\')) => '\\',
+                Some((_, 'n')) => '\n',
+                _ => return Err(offset),
+            },
+            c => c,
+        });
+    }
+    Ok(result)
+}
+
+/// Reads a registry of answers, one `day part input answer` per line.
+///
+/// Line breaks in answers are written as `\n`, backslashes as `\\`.
+pub fn read_answers(input: &Input) -> Result<Vec<Answer>, Box<dyn Error>> {
+    let mut answers = vec![];
+    let lines = input.lines().map_err(|err| format!("{}: {}", input, err))?;
+    for (index, line) in lines.enumerate() {
+        let line = line?;
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parser = pair(
+            number(),
+            pair(
+                preceded(tag(" "), one_of(PARTS)),
+                pair(preceded(tag(" "), word()), preceded(tag(" "), rest())),
+            ),
+        );
+        let (day, (part, (path, escaped))) =
+            parse_line(index + 1, &line, parser).map_err(|err| err.in_file(input.to_string()))?;
+        let answer = unescape(escaped).map_err(|offset| {
+            ParseError::new(
+                ParseErrorKind::UnexpectedToken,
+                "expected `\\\\` or `\\n` after `\\`",
+            )
+            .at(index + 1, &line)
+            .at_offset(line.len() - escaped.len() + offset)
+            .in_file(input.to_string())
+        })?;
+        answers.push(Answer {
+            day,
+            part,
+            input: path.to_string(),
+            answer,
+        });
+    }
+    Ok(answers)
+}
+
+pub fn write_answers<W>(mut writer: W, answers: &[Answer]) -> io::Result<()>
+where
+    W: Write,
+{
+    writeln!(writer, "# day part input answer")?;
+    for answer in answers {
+        writeln!(
+            writer,
+            "{} {} {} {}",
+            answer.day,
+            answer.part,
+            answer.input,
+            escape(&answer.answer)
+        )?;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    /// The solver returned a different answer.
+    Mismatch(String),
+    /// The input could not be parsed or solved.
+    Fail(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub answer: Answer,
+    pub outcome: Outcome,
+}
+
+/// The directory the inputs of a registry are relative to: the one it is in, or the
+/// current directory if it is not read from a file.
+pub fn base_dir(registry: &Input) -> PathBuf {
+    match registry {
+        Input::File(path) | Input::Gzip(path) => {
+            path.parent().map(Path::to_path_buf).unwrap_or_default()
+        }
+        Input::Stdin | Input::Text(_) => PathBuf::new(),
+    }
+}
+
+/// Solves every registered answer again, parsing each input only once.
+///
+/// Inputs are looked up relative to `base`, see [`base_dir`]. A panicking solver fails
+/// its check, but does not stop the others.
+pub fn verify(answers: &[Answer], base: &Path) -> Vec<Check> {
+    let mut parsed = HashMap::new();
+    answers
+        .iter()
+        .map(|answer| {
+            let outcome = match solver(answer.day) {
+                None => Outcome::Fail(format!("no solution for day {}", answer.day)),
+                Some(solver) => {
+                    let input = parsed
+                        .entry((answer.day, answer.input.as_str()))
+                        .or_insert_with(|| {
+                            let input = Input::path(base.join(&answer.input));
+                            panic::catch_unwind(AssertUnwindSafe(|| {
+                                solver.parse_input(&input, &mut Diagnostics::default())
+                            }))
+                            .map_err(panic_message)?
+                            .map_err(|err| err.to_string())
+                        });
+                    match input {
+                        Err(err) => Outcome::Fail(err.clone()),
+                        Ok(input) => {
+                            let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+                                solver.solve(input.as_ref(), answer.part, &Params::default())
+                            }));
+                            match solved {
+                                Err(payload) => Outcome::Fail(panic_message(payload)),
+                                Ok(Err(err)) => Outcome::Fail(err.to_string()),
+                                Ok(Ok(actual)) if actual == answer.answer => Outcome::Pass,
+                                Ok(Ok(actual)) => Outcome::Mismatch(actual),
+                            }
+                        }
+                    }
+                }
+            };
+            Check {
+                answer: answer.clone(),
+                outcome,
+            }
+        })
+        .collect()
+}
+
+/// Prints the outcome of every check as a table.
+pub fn print_report<W>(mut writer: W, checks: &[Check]) -> io::Result<()>
+where
+    W: Write,
+{
+    let width = checks
+        .iter()
+        .map(|check| check.answer.input.len())
+        .max()
+        .unwrap_or(0)
+        .max("input".len());
+    writeln!(
+        writer,
+        "{:>3}  {:<4}  {:<width$}  {:<8}  details",
+        "day", "part", "input", "status"
+    )?;
+    for Check { answer, outcome } in checks {
+        let (status, details) = match outcome {
+            Outcome::Pass => ("pass", escape(&answer.answer)),
+            Outcome::Mismatch(actual) => (
+                "mismatch",
+                format!(
+                    "expected {}, got {}",
+                    escape(&answer.answer),
+                    escape(actual)
+                ),
+            ),
+            Outcome::Fail(err) => ("fail", err.lines().next().unwrap_or_default().to_string()),
+        };
+        writeln!(
+            writer,
+            "{:>3}  {:<4}  {:<width$}  {:<8}  {}",
+            answer.day, answer.part, answer.input, status, details
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_round_trip() {
+        let answers = vec![
+            Answer {
+                day: 1,
+                part: Part::Two,
+                input: "day01/test_input.txt".to_string(),
+                answer: "45000".to_string(),
+            },
+            Answer {
+                day: 10,
+                part: Part::Two,
+                input: "day10/input.txt".to_string(),
+                answer: "#.\\\n.#".to_string(),
+            },
+        ];
+
+        let mut file = vec![];
+        write_answers(&mut file, &answers).unwrap();
+        let file = String::from_utf8(file).unwrap();
+        assert!(file.ends_with("10 2 day10/input.txt #.\\\\\\n.#\n"));
+        assert_eq!(read_answers(&Input::text(&file)).unwrap(), answers);
+    }
+
+    #[test]
+    fn reports_bad_escapes() {
+        let err = read_answers(&Input::text("10 2 input.txt ab\\x")).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((err.line(), err.column()), (Some(1), Some(18)));
+    }
+
+    #[test]
+    fn registry_covers_every_day() {
+        let answers = read_answers(&Input::path("../answers.txt")).unwrap();
+        for day in crate::DAYS {
+            for part in [Part::One, Part::Two] {
+                assert!(
+                    answers.iter().any(|a| a.day == day && a.part == part),
+                    "no answer for day {} part {}",
+                    day,
+                    part
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn inputs_are_relative_to_the_registry_and_panics_fail() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-verify-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // The item of worry level 0 divides by zero on the first inspection.
+        std::fs::write(
+            dir.join("monkeys.txt"),
+            "Monkey 0:\n  Starting items: 0\n  Operation: new = old / old\n  \
+             Test: divisible by 2\n    If true: throw to monkey 1\n    \
+             If false: throw to monkey 1\n\n\
+             Monkey 1:\n  Starting items: 1\n  Operation: new = old + 1\n  \
+             Test: divisible by 2\n    If true: throw to monkey 0\n    \
+             If false: throw to monkey 0\n",
+        )
+        .unwrap();
+        let registry = dir.join("answers.txt");
+        std::fs::write(&registry, "11 1 monkeys.txt 1\n11 2 missing.txt 1\n").unwrap();
+
+        let registry = Input::path(&registry);
+        let answers = read_answers(&registry).unwrap();
+        let checks = verify(&answers, &base_dir(&registry));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(
+            matches!(&checks[0].outcome, Outcome::Fail(err) if err.starts_with("panicked")),
+            "{:?}",
+            checks[0]
+        );
+        assert!(matches!(&checks[1].outcome, Outcome::Fail(err) if err.contains("missing.txt")));
+    }
+}
@@ -0,0 +1,162 @@
+use std::{error::Error, io::BufRead};
+
+use aoc2022::{
+    detect::{is_digits, share_of_lines},
+    parse_number, Detect, Diagnostics, Generate, Param, Parameter, Params, Solution, TopK,
+};
+use rand::{Rng, RngCore};
+
+pub struct Day01;
+
+/// The `len` largest amounts of calories, largest first.
+fn get_maxima(calories: &[usize], len: usize) -> Vec<usize> {
+    let mut elves = TopK::largest(len);
+    elves.extend(calories.iter().copied());
+    elves.into_sorted_vec()
+}
+
+const TOP: Parameter<usize> = Parameter::new(
+    "top",
+    "3",
+    "number of elves whose calories are added up in part 2",
+);
+
+impl Solution for Day01 {
+    /// Total calories carried by each elf, in input order.
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const PARAMETERS: &'static [&'static dyn Param] = &[&TOP];
+
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        let mut elves: Vec<usize> = vec![];
+        let mut accumulator = 0;
+        for (index, line) in reader.lines().enumerate() {
+            let value = line?;
+            if value.is_empty() {
+                elves.push(accumulator);
+                accumulator = 0;
+            } else if let Some(calories) = diagnostics.check(
+                parse_number::<usize>(&value).map_err(|err| err.at(index + 1, &value).at_column(1)),
+            ) {
+                accumulator += calories;
+            }
+        }
+
+        elves.push(accumulator);
+
+        Ok(elves)
+    }
+
+    fn part1_with(input: &Self::Input, _params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        Ok(get_maxima(input, 1).first().copied().unwrap_or(0))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        Ok(get_maxima(input, params.get(&TOP)?).iter().sum())
+    }
+}
+
+/// Generates `size` elves, each carrying a handful of snacks.
+impl Generate for Day01 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        let mut input = String::new();
+        for elf in 0..size.max(1) {
+            if elf > 0 {
+                input.push('\n');
+            }
+            for _ in 0..rng.gen_range(1..=10) {
+                input.push_str(&format!("{}\n", rng.gen_range(1000..=60000)));
+            }
+        }
+        input
+    }
+}
+
+/// Calories are short numbers, in groups separated by empty lines.
+impl Detect for Day01 {
+    fn detect(&self, sample: &str) -> f64 {
+        let groups = if sample.trim().contains("\n\n") {
+            1.0
+        } else {
+            0.5
+        };
+        groups * share_of_lines(sample, |line| is_digits(line) && line.len() <= 9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc2022::{Input, ParseMode};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn naive_maxima(calories: &[usize], len: usize) -> Vec<usize> {
+        let mut sorted = calories.to_vec();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        sorted.truncate(len);
+        sorted
+    }
+
+    #[test]
+    fn sums_are_correct_and_sorted() {
+        let calories = Day01::parse_file("test_input.txt").unwrap();
+        let elves = get_maxima(&calories, usize::MAX);
+        assert_eq!(elves.len(), 5);
+        assert_eq!(elves[0], 24000);
+        assert_eq!(elves[1], 11000);
+        assert_eq!(elves[2], 10000);
+        assert_eq!(elves[3], 6000);
+        assert_eq!(elves[4], 4000);
+    }
+
+    #[test]
+    fn top_elves() {
+        let calories = Day01::parse_file("test_input.txt").unwrap();
+        assert_eq!(Day01::part1(&calories).unwrap(), 24000);
+        assert_eq!(Day01::part2(&calories).unwrap(), 45000);
+    }
+
+    #[test]
+    fn maxima_keep_ties() {
+        assert_eq!(get_maxima(&[3, 5, 5, 1], 3), vec![5, 5, 3]);
+        assert_eq!(Day01::part2(&vec![2, 4, 4, 4]).unwrap(), 12);
+        assert_eq!(get_maxima(&[0, 2, 0], 3), vec![2, 0, 0]);
+    }
+
+    #[test]
+    fn lenient_parsing_skips_bad_lines() {
+        let input = Input::text("1000\nabc\n2000\n\n3000\n");
+        assert!(Day01::parse_input(input.clone()).is_err());
+
+        let mut diagnostics = Diagnostics::new(ParseMode::Lenient);
+        let calories = Day01::parse_input_with(input, &mut diagnostics).unwrap();
+        assert_eq!(calories.iter().sum::<usize>(), 6000);
+        assert_eq!(diagnostics.errors().len(), 1);
+        assert_eq!(diagnostics.errors()[0].line(), Some(2));
+    }
+
+    proptest! {
+        #[test]
+        fn maxima_match_naive_maxima(
+            // Few distinct values, so that elves often carry the same amount.
+            calories in prop::collection::vec(0..8_usize, 0..30),
+            len in 0..6_usize,
+        ) {
+            prop_assert_eq!(get_maxima(&calories, len), naive_maxima(&calories, len));
+        }
+
+        #[test]
+        fn answers_match_naive_answers(seed: u64, size in 1..50_usize) {
+            let calories = Day01::parse_str(&Day01.generate_seeded(seed, size)).unwrap();
+            let top = naive_maxima(&calories, 3);
+            prop_assert_eq!(Day01::part1(&calories).unwrap(), top[0]);
+            prop_assert_eq!(Day01::part2(&calories).unwrap(), top.iter().sum::<usize>());
+        }
+    }
+}
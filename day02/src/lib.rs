@@ -0,0 +1,255 @@
+use std::{error::Error, io::BufRead};
+
+use aoc2022::{
+    detect::share_of_lines, Detect, Diagnostics, Generate, Params, ParseError, ParseErrorKind,
+    Solution,
+};
+use rand::{Rng, RngCore};
+
+pub struct Day02;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Hand {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Hand {
+    fn loser_again(hand: Self) -> Self {
+        match hand {
+            Self::Rock => Self::Scissors,
+            Self::Paper => Self::Rock,
+            Self::Scissors => Self::Paper,
+        }
+    }
+
+    fn winner_again(hand: Self) -> Self {
+        match hand {
+            Self::Rock => Self::Paper,
+            Self::Paper => Self::Scissors,
+            Self::Scissors => Self::Rock,
+        }
+    }
+
+    fn points(self) -> usize {
+        match self {
+            Hand::Rock => 1,
+            Hand::Paper => 2,
+            Hand::Scissors => 3,
+        }
+    }
+
+    fn parse_opponent_hand(value: &str) -> Option<Self> {
+        match value {
+            "A" => Some(Self::Rock),
+            "B" => Some(Self::Paper),
+            "C" => Some(Self::Scissors),
+            _ => None,
+        }
+    }
+}
+
+/// Second column of the strategy guide, which means something different in each part.
+#[derive(Copy, Clone, PartialEq)]
+enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl Column {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "X" => Some(Self::X),
+            "Y" => Some(Self::Y),
+            "Z" => Some(Self::Z),
+            _ => None,
+        }
+    }
+}
+
+pub struct Round {
+    opponent_hand: Hand,
+    column: Column,
+}
+
+fn parse_round(line_number: usize, round: &str) -> Result<Round, ParseError> {
+    let (opponent, column) = round.split_once(' ').ok_or_else(|| {
+        ParseError::new(
+            ParseErrorKind::InvalidLine,
+            "expected opponent hand and strategy",
+        )
+        .at(line_number, round)
+    })?;
+    let opponent_hand = Hand::parse_opponent_hand(opponent).ok_or_else(|| {
+        ParseError::new(
+            ParseErrorKind::UnexpectedToken,
+            format!(
+                "expected one of `A`, `B`, `C` for opponent hand, found `{}`",
+                opponent
+            ),
+        )
+        .at(line_number, round)
+        .at_column(1)
+    })?;
+    let column = Column::parse(column).ok_or_else(|| {
+        ParseError::new(
+            ParseErrorKind::UnexpectedToken,
+            format!(
+                "expected one of `X`, `Y`, `Z` for strategy, found `{}`",
+                column
+            ),
+        )
+        .at(line_number, round)
+        .at_offset(opponent.len() + 1)
+    })?;
+    Ok(Round {
+        opponent_hand,
+        column,
+    })
+}
+
+fn part1_strategy(column: Column, _opponent_hand: Hand) -> Hand {
+    match column {
+        Column::X => Hand::Rock,
+        Column::Y => Hand::Paper,
+        Column::Z => Hand::Scissors,
+    }
+}
+
+fn part2_strategy(column: Column, opponent_hand: Hand) -> Hand {
+    match column {
+        Column::X => Hand::loser_again(opponent_hand),
+        Column::Y => opponent_hand,
+        Column::Z => Hand::winner_again(opponent_hand),
+    }
+}
+
+fn process_round<F>(round: &Round, strategy: F) -> usize
+where
+    F: Fn(Column, Hand) -> Hand,
+{
+    let opponent_hand = round.opponent_hand;
+    let player_hand = strategy(round.column, opponent_hand);
+    let mut score = player_hand.points();
+    if Hand::loser_again(player_hand) == opponent_hand {
+        score += 6
+    } else if player_hand == opponent_hand {
+        score += 3
+    }
+    score
+}
+
+fn process_rounds<F>(rounds: &[Round], strategy: F) -> usize
+where
+    F: Fn(Column, Hand) -> Hand,
+{
+    rounds
+        .iter()
+        .map(|round| process_round(round, &strategy))
+        .sum()
+}
+
+impl Solution for Day02 {
+    type Input = Vec<Round>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        let mut rounds = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if !line.is_empty() {
+                rounds.extend(diagnostics.check(parse_round(index + 1, &line)));
+            }
+        }
+        Ok(rounds)
+    }
+
+    fn part1_with(input: &Self::Input, _params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        Ok(process_rounds(input, part1_strategy))
+    }
+
+    fn part2_with(input: &Self::Input, _params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        Ok(process_rounds(input, part2_strategy))
+    }
+}
+
+/// Generates a strategy guide of `size` rounds.
+impl Generate for Day02 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let opponent = (b'A' + rng.gen_range(0..3)) as char;
+                let response = (b'X' + rng.gen_range(0..3)) as char;
+                format!("{} {}\n", opponent, response)
+            })
+            .collect()
+    }
+}
+
+/// Every line is a round like `A Y`.
+impl Detect for Day02 {
+    fn detect(&self, sample: &str) -> f64 {
+        share_of_lines(sample, |line| {
+            matches!(line.as_bytes(), [b'A'..=b'C', b' ', b'X'..=b'Z'])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc2022::ParseErrors;
+
+    use super::*;
+
+    #[test]
+    fn part1_strategy_rock_vs_scissors() {
+        let round = parse_round(1, "A Y").unwrap();
+        assert_eq!(process_round(&round, part1_strategy), 8);
+    }
+
+    #[test]
+    fn part1_strategy_scissors_vs_rock() {
+        let round = parse_round(1, "B X").unwrap();
+        assert_eq!(process_round(&round, part1_strategy), 1);
+    }
+
+    #[test]
+    fn part1_strategy_paper_vs_paper() {
+        let round = parse_round(1, "C Z").unwrap();
+        assert_eq!(process_round(&round, part1_strategy), 6);
+    }
+
+    #[test]
+    fn part2_strategy_rock_vs_scissors() {
+        let round = parse_round(1, "A Y").unwrap();
+        assert_eq!(process_round(&round, part2_strategy), 4);
+    }
+
+    #[test]
+    fn part2_strategy_scissors_vs_rock() {
+        let round = parse_round(1, "B X").unwrap();
+        assert_eq!(process_round(&round, part2_strategy), 1);
+    }
+
+    #[test]
+    fn part2_strategy_paper_vs_paper() {
+        let round = parse_round(1, "C Z").unwrap();
+        assert_eq!(process_round(&round, part2_strategy), 7);
+    }
+
+    #[test]
+    fn invalid_strategy_is_reported_with_position() {
+        let err = Day02::parse_str("A Y\nB W\n").err().unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let err = &errors.errors()[0];
+        assert_eq!(err.kind(), ParseErrorKind::UnexpectedToken);
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.column(), Some(3));
+    }
+}
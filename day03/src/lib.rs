@@ -0,0 +1,147 @@
+use std::{collections::HashSet, error::Error, io::BufRead};
+
+use aoc2022::{
+    detect::share_of_lines, Detect, Diagnostics, Generate, Params, ParseError, ParseErrorKind,
+    Solution,
+};
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+pub struct Day03;
+
+fn parse_rucksack(line: &str) -> (HashSet<char>, HashSet<char>) {
+    let item_count = line.len();
+    assert!(item_count.is_multiple_of(2));
+    let compartment_size = item_count / 2;
+    (
+        line[..compartment_size].chars().collect(),
+        line[compartment_size..].chars().collect(),
+    )
+}
+
+fn parse_rucksack_items(line_number: usize, line: &str) -> Result<String, ParseError> {
+    if let Some((offset, item)) = line
+        .char_indices()
+        .find(|(_, item)| !item.is_ascii_alphabetic())
+    {
+        return Err(ParseError::new(
+            ParseErrorKind::UnexpectedToken,
+            format!("unsupported item: {}", item),
+        )
+        .at(line_number, line)
+        .at_offset(offset));
+    }
+    if !line.len().is_multiple_of(2) {
+        return Err(ParseError::new(
+            ParseErrorKind::InvalidLine,
+            format!(
+                "odd number of items ({}) cannot be split into compartments",
+                line.len()
+            ),
+        )
+        .at(line_number, line));
+    }
+    Ok(line.to_string())
+}
+
+fn get_priorty(item: &char) -> Result<i32, String> {
+    match item {
+        'a'..='z' => Ok(*item as i32 - 'a' as i32 + 1),
+        'A'..='Z' => Ok(*item as i32 - 'A' as i32 + 1 + 26),
+        _ => Err(format!("unsupported item: {}", item)),
+    }
+}
+
+impl Solution for Day03 {
+    type Input = Vec<String>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        let mut rucksacks = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if !line.is_empty() {
+                rucksacks.extend(diagnostics.check(parse_rucksack_items(index + 1, &line)));
+            }
+        }
+        Ok(rucksacks)
+    }
+
+    fn part1_with(input: &Self::Input, _params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        let mut priotity_sum = 0;
+        for line in input {
+            let compartments = parse_rucksack(line);
+            let intersect = compartments.0.intersection(&compartments.1);
+            let priority = intersect.map(get_priorty).sum::<Result<i32, _>>()?;
+            priotity_sum += priority;
+        }
+
+        Ok(priotity_sum)
+    }
+
+    fn part2_with(input: &Self::Input, _params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        let mut priotity_sum = 0;
+        for (group, chunk) in input.iter().chunks(3).into_iter().enumerate() {
+            let rucksacks = chunk.map(|items| items.chars().collect::<HashSet<_>>());
+            let intersect = rucksacks
+                .reduce(|a, b| a.intersection(&b).copied().collect())
+                .unwrap();
+            let badge = intersect
+                .iter()
+                .exactly_one()
+                .map_err(|_| format!("group {} does not share exactly one item", group + 1))?;
+            priotity_sum += get_priorty(badge)?;
+        }
+
+        Ok(priotity_sum)
+    }
+}
+
+/// Generates `size` groups of three rucksacks.
+///
+/// Apart from the badge of its group, every rucksack only holds items from its own third of
+/// the alphabet, and only one of those ends up in both compartments.
+impl Generate for Day03 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let mut items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+            items.shuffle(rng);
+            let (badge, rest) = items.split_first().unwrap();
+            for items in rest.chunks(rest.len() / 3) {
+                let (shared, items) = items.split_first().unwrap();
+                let (left, right) = items.split_at(items.len() / 2);
+                let half = rng.gen_range(2..=16);
+                let mut first = vec![*shared, *badge];
+                first.extend((2..half).map(|_| *left.choose(rng).unwrap()));
+                let mut second = vec![*shared];
+                second.extend((1..half).map(|_| *right.choose(rng).unwrap()));
+                first.shuffle(rng);
+                second.shuffle(rng);
+                input.extend(first);
+                input.extend(second);
+                input.push('\n');
+            }
+        }
+        input
+    }
+}
+
+/// Rucksacks are lines of an even number of letters, which are both lower and upper case.
+impl Detect for Day03 {
+    fn detect(&self, sample: &str) -> f64 {
+        let cases = if sample.contains(|c: char| c.is_ascii_uppercase()) {
+            1.0
+        } else {
+            0.5
+        };
+        cases
+            * share_of_lines(sample, |line| {
+                line.len().is_multiple_of(2) && line.bytes().all(|byte| byte.is_ascii_alphabetic())
+            })
+    }
+}
@@ -0,0 +1,158 @@
+use std::{error::Error, io::BufRead};
+
+use aoc2022::{
+    detect::{is_digits, share_of_lines},
+    parser::{label, number, parse_line, separated_pair, tag, try_map},
+    Detect, Diagnostics, Generate, Interval, Params, ParseError, ParseErrorKind, Solution,
+};
+use rand::{Rng, RngCore};
+
+pub struct Day04;
+
+pub struct Assignment {
+    first: Interval<i32>,
+    second: Interval<i32>,
+}
+
+fn parse_assignment(line_number: usize, line: &str) -> Result<Assignment, ParseError> {
+    let range = || {
+        try_map(
+            separated_pair(number(), tag("-"), number()),
+            |(from, until)| {
+                Interval::new(from, until).ok_or_else(|| {
+                    ParseError::new(
+                        ParseErrorKind::InvalidValue,
+                        format!("section range `{}-{}` ends before it starts", from, until),
+                    )
+                })
+            },
+        )
+    };
+    let assignment = label(
+        separated_pair(range(), tag(","), range()),
+        "two section ranges like `2-4,6-8`",
+    );
+
+    let (first, second) = parse_line(line_number, line, assignment)?;
+    Ok(Assignment { first, second })
+}
+
+impl Solution for Day04 {
+    type Input = Vec<Assignment>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        let mut assignments = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if !line.is_empty() {
+                assignments.extend(diagnostics.check(parse_assignment(index + 1, &line)));
+            }
+        }
+        Ok(assignments)
+    }
+
+    fn part1_with(input: &Self::Input, _params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        Ok(input
+            .iter()
+            .filter(|Assignment { first, second }| {
+                first.contains_interval(second) || second.contains_interval(first)
+            })
+            .count())
+    }
+
+    fn part2_with(input: &Self::Input, _params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        Ok(input
+            .iter()
+            .filter(|Assignment { first, second }| first.overlaps(second))
+            .count())
+    }
+}
+
+/// Generates `size` pairs of section assignments.
+impl Generate for Day04 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        let mut assignment = || {
+            let start = rng.gen_range(1..=99);
+            format!("{}-{}", start, rng.gen_range(start..=99))
+        };
+        (0..size)
+            .map(|_| format!("{},{}\n", assignment(), assignment()))
+            .collect()
+    }
+}
+
+/// Every line is a pair of ranges like `2-4,6-8`.
+impl Detect for Day04 {
+    fn detect(&self, sample: &str) -> f64 {
+        let range = |range: &str| {
+            range
+                .split_once('-')
+                .is_some_and(|(from, until)| is_digits(from) && is_digits(until))
+        };
+        share_of_lines(sample, |line| {
+            line.split_once(',')
+                .is_some_and(|(first, second)| range(first) && range(second))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc2022::ParseErrors;
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let assignments = Day04::parse_file("input_test.txt").unwrap();
+        assert_eq!(Day04::part1(&assignments).unwrap(), 2);
+        assert_eq!(Day04::part2(&assignments).unwrap(), 4);
+    }
+
+    #[test]
+    fn backwards_range_is_reported_with_position() {
+        let err = Day04::parse_str("5-3,1-2\n").err().unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let err = &errors.errors()[0];
+        assert_eq!(err.kind(), ParseErrorKind::InvalidValue);
+        assert_eq!((err.line(), err.column()), (Some(1), Some(1)));
+
+        let err = Day04::parse_str("2-4,6-8\n2-4;6-8\n").err().unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let err = &errors.errors()[0];
+        assert_eq!(err.kind(), ParseErrorKind::UnexpectedToken);
+        assert_eq!((err.line(), err.column()), (Some(2), Some(4)));
+    }
+
+    #[test]
+    fn intervals_match_comparing_endpoints() {
+        let input = Day04.generate_seeded(4, 1000);
+        let ranges = input
+            .lines()
+            .map(|line| {
+                let numbers = line
+                    .split([',', '-'])
+                    .map(|number| number.parse::<i32>().unwrap())
+                    .collect::<Vec<_>>();
+                (numbers[0], numbers[1], numbers[2], numbers[3])
+            })
+            .collect::<Vec<_>>();
+        let contained = ranges
+            .iter()
+            .filter(|&&(a, b, c, d)| a <= c && d <= b || c <= a && b <= d)
+            .count();
+        let overlapping = ranges
+            .iter()
+            .filter(|&&(a, b, c, d)| a <= d && c <= b)
+            .count();
+
+        let assignments = Day04::parse_str(&input).unwrap();
+        assert_eq!(Day04::part1(&assignments).unwrap(), contained);
+        assert_eq!(Day04::part2(&assignments).unwrap(), overlapping);
+    }
+}
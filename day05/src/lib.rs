@@ -0,0 +1,414 @@
+use std::{error::Error, fmt, io::BufRead};
+
+use aoc2022::{
+    detect::{is_digits, share_of_lines},
+    event,
+    parser::{
+        alt, delimited, label, map, number, pair, parse_line, preceded, satisfy, separated, tag,
+        try_map,
+    },
+    simulate::run_to_end,
+    trace::Level,
+    Detect, Diagnostics, Generate, Params, ParseError, ParseErrorKind, Part, Simulate, Simulation,
+    Solution, Stepwise,
+};
+use rand::{seq::SliceRandom, Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+pub struct Day05;
+
+#[derive(Copy, Clone, Debug)]
+pub struct Move {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+pub struct Procedure {
+    stacks: Vec<Vec<char>>,
+    moves: Vec<Move>,
+}
+
+fn top_crates(stacks: &[Vec<char>]) -> String {
+    stacks
+        .iter()
+        .map(|stack| stack.last().unwrap_or(&' '))
+        .collect()
+}
+
+/// The crane models of the two parts.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Model {
+    /// Moves crates one at a time, reversing their order.
+    CrateMover9000,
+    /// Moves several crates at once, keeping their order.
+    CrateMover9001,
+}
+
+/// A crane working through the procedure one move at a time.
+#[derive(Clone, Debug)]
+pub struct Crane {
+    model: Model,
+    stacks: Vec<Vec<char>>,
+    moves: Vec<Move>,
+    /// The number of moves done.
+    done: usize,
+}
+
+impl Crane {
+    pub fn new(procedure: &Procedure, model: Model) -> Self {
+        Self {
+            model,
+            stacks: procedure.stacks.clone(),
+            moves: procedure.moves.clone(),
+            done: 0,
+        }
+    }
+
+    pub fn top_crates(&self) -> String {
+        top_crates(&self.stacks)
+    }
+}
+
+/// What changes while a [`Crane`] works through the procedure.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CraneSnapshot {
+    pub stacks: Vec<Vec<char>>,
+    /// The number of moves done.
+    pub done: usize,
+}
+
+impl Simulation for Crane {
+    /// The number of moves done, which never repeats.
+    type Key = usize;
+
+    /// Does the next move.
+    fn step(&mut self) {
+        let Move { count, from, to } = self.moves[self.done];
+        let from_stack = &mut self.stacks[from - 1];
+        let mut moved = from_stack.split_off(from_stack.len().saturating_sub(count));
+        if self.model == Model::CrateMover9000 {
+            moved.reverse();
+        }
+        self.stacks[to - 1].extend(moved);
+        self.done += 1;
+        event!(
+            Level::Debug,
+            "move",
+            count = count,
+            from = from,
+            to = to,
+            top = self.top_crates()
+        );
+    }
+
+    fn is_done(&self) -> bool {
+        self.done == self.moves.len()
+    }
+
+    fn key(&self) -> usize {
+        self.done
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        Some(self.moves.len() - self.done)
+    }
+}
+
+impl Stepwise for Crane {
+    type Snapshot = CraneSnapshot;
+
+    fn values(&self) -> Vec<(String, i64)> {
+        let mut values = vec![("moves".to_string(), self.done as i64)];
+        for (index, stack) in self.stacks.iter().enumerate() {
+            values.push((format!("stack{}", index + 1), stack.len() as i64));
+        }
+        values
+    }
+
+    fn snapshot(&self) -> CraneSnapshot {
+        CraneSnapshot {
+            stacks: self.stacks.clone(),
+            done: self.done,
+        }
+    }
+
+    fn restore(&mut self, snapshot: CraneSnapshot) -> Result<(), Box<dyn Error>> {
+        let crates = |stacks: &[Vec<char>]| stacks.iter().map(Vec::len).sum::<usize>();
+        if snapshot.stacks.len() != self.stacks.len() {
+            return Err(format!(
+                "the snapshot has {} stacks, expected {}",
+                snapshot.stacks.len(),
+                self.stacks.len()
+            )
+            .into());
+        }
+        if crates(&snapshot.stacks) != crates(&self.stacks) {
+            return Err(format!(
+                "the snapshot has {} crates, expected {}",
+                crates(&snapshot.stacks),
+                crates(&self.stacks)
+            )
+            .into());
+        }
+        if snapshot.done > self.moves.len() {
+            return Err(format!(
+                "the snapshot has {} moves done, but there are only {}",
+                snapshot.done,
+                self.moves.len()
+            )
+            .into());
+        }
+        self.stacks = snapshot.stacks;
+        self.done = snapshot.done;
+        Ok(())
+    }
+}
+
+/// Draws the stacks like the puzzle does, followed by the next move.
+impl fmt::Display for Crane {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let row = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => "   ".to_string(),
+                })
+                .collect::<Vec<_>>();
+            writeln!(f, "{}", row.join(" ").trim_end())?;
+        }
+        let numbers = (1..=self.stacks.len())
+            .map(|stack| format!(" {} ", stack))
+            .collect::<Vec<_>>();
+        writeln!(f, "{}", numbers.join(" "))?;
+        match self.moves.get(self.done) {
+            Some(Move { count, from, to }) => write!(
+                f,
+                "next, {} of {}: move {} from {} to {}",
+                self.done + 1,
+                self.moves.len(),
+                count,
+                from,
+                to
+            ),
+            None => write!(f, "all {} moves done", self.moves.len()),
+        }
+    }
+}
+
+fn parse_move(line_number: usize, line: &str, stack_count: usize) -> Result<Move, ParseError> {
+    let stack = || {
+        try_map(number(), move |stack: usize| {
+            if (1..=stack_count).contains(&stack) {
+                Ok(stack)
+            } else {
+                Err(ParseError::new(
+                    ParseErrorKind::InvalidValue,
+                    format!("no stack {}, expected 1 to {}", stack, stack_count),
+                ))
+            }
+        })
+    };
+    let parser = label(
+        pair(
+            preceded(tag("move "), number()),
+            pair(
+                preceded(tag(" from "), stack()),
+                preceded(tag(" to "), stack()),
+            ),
+        ),
+        "a move like `move 1 from 2 to 3`",
+    );
+
+    let (count, (from, to)) = parse_line(line_number, line, parser)?;
+    Ok(Move { count, from, to })
+}
+
+/// Parses a row of the drawing, with a crate or an empty slot for every stack.
+fn parse_crates(line_number: usize, line: &str) -> Result<Vec<Option<char>>, ParseError> {
+    let crate_ = delimited(
+        tag("["),
+        satisfy(|c| c.is_ascii_uppercase(), "a crate letter"),
+        tag("]"),
+    );
+    let slot = label(
+        alt(map(crate_, Some), map(tag("   "), |_| None)),
+        "a crate like `[A]` or an empty slot",
+    );
+
+    parse_line(line_number, line, separated(slot, tag(" ")))
+}
+
+impl Solution for Day05 {
+    type Input = Procedure;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        let mut stacks: Vec<Vec<char>> = vec![];
+        let mut moves = vec![];
+        let mut in_drawing = true;
+        for (index, line) in reader.lines().enumerate() {
+            let value = line?;
+            if value.is_empty() {
+                in_drawing = false;
+            } else if !in_drawing {
+                moves.extend(diagnostics.check(parse_move(index + 1, &value, stacks.len())));
+            } else if value.chars().all(|c| c == ' ' || c.is_ascii_digit()) {
+                // stack numbers below the drawing
+            } else if let Some(row) = diagnostics.check(parse_crates(index + 1, &value)) {
+                if stacks.len() < row.len() {
+                    stacks.resize(row.len(), vec![]);
+                }
+                for (stack, c) in stacks.iter_mut().zip(row) {
+                    if let Some(c) = c {
+                        stack.insert(0, c);
+                    }
+                }
+            }
+        }
+
+        Ok(Procedure { stacks, moves })
+    }
+
+    fn part1_with(input: &Self::Input, _params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        let mut crane = Crane::new(input, Model::CrateMover9000);
+        run_to_end(&mut crane);
+        Ok(crane.top_crates())
+    }
+
+    fn part2_with(input: &Self::Input, _params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        let mut crane = Crane::new(input, Model::CrateMover9001);
+        run_to_end(&mut crane);
+        Ok(crane.top_crates())
+    }
+}
+
+impl Simulate for Day05 {
+    type State = Crane;
+
+    fn start(input: &Self::Input, part: Part, _params: &Params) -> Result<Crane, Box<dyn Error>> {
+        let model = match part {
+            Part::One => Model::CrateMover9000,
+            Part::Two => Model::CrateMover9001,
+        };
+        Ok(Crane::new(input, model))
+    }
+}
+
+/// Generates a drawing of up to nine stacks, taller for larger sizes, followed by `size`
+/// moves that never take more crates than a stack holds.
+impl Generate for Day05 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        let mut heights = (0..rng.gen_range(2..=9))
+            .map(|_| rng.gen_range(1..=8 + size / 20))
+            .collect::<Vec<usize>>();
+
+        let mut input = String::new();
+        for level in (0..*heights.iter().max().unwrap()).rev() {
+            let row = heights
+                .iter()
+                .map(|&height| {
+                    if height > level {
+                        format!("[{}]", (b'A' + rng.gen_range(0..26)) as char)
+                    } else {
+                        "   ".to_string()
+                    }
+                })
+                .collect::<Vec<_>>();
+            input.push_str(&row.join(" "));
+            input.push('\n');
+        }
+        let labels = (1..=heights.len())
+            .map(|stack| format!(" {} ", stack))
+            .collect::<Vec<_>>();
+        input.push_str(&labels.join(" "));
+        input.push_str("\n\n");
+
+        for _ in 0..size {
+            let stacks = (0..heights.len())
+                .filter(|&stack| heights[stack] > 0)
+                .collect::<Vec<_>>();
+            let from = *stacks.choose(rng).unwrap();
+            let to = (from + rng.gen_range(1..heights.len())) % heights.len();
+            let count = rng.gen_range(1..=heights[from]);
+            heights[from] -= count;
+            heights[to] += count;
+            input.push_str(&format!("move {} from {} to {}\n", count, from + 1, to + 1));
+        }
+        input
+    }
+}
+
+/// A drawing of crates and stack numbers, followed by moves.
+impl Detect for Day05 {
+    fn detect(&self, sample: &str) -> f64 {
+        let crates = |line: &str| {
+            line.contains('[')
+                && line
+                    .chars()
+                    .all(|c| matches!(c, ' ' | '[' | ']' | 'A'..='Z'))
+        };
+        let stacks = |line: &str| {
+            line.starts_with(' ')
+                && line.split_whitespace().count() > 1
+                && line.split_whitespace().all(is_digits)
+        };
+        let step = |line: &str| {
+            matches!(
+                line.split(' ').collect::<Vec<_>>()[..],
+                ["move", count, "from", from, "to", to]
+                    if is_digits(count) && is_digits(from) && is_digits(to)
+            )
+        };
+        share_of_lines(sample, |line| crates(line) || stacks(line) || step(line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc2022::ParseErrors;
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let procedure = Day05::parse_file("input_test.txt").unwrap();
+        assert_eq!(
+            procedure.stacks,
+            [vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        assert_eq!(procedure.moves.len(), 4);
+        assert_eq!(Day05::part1(&procedure).unwrap(), "CMZ");
+        assert_eq!(Day05::part2(&procedure).unwrap(), "MCD");
+    }
+
+    #[test]
+    fn invalid_stack_is_reported_with_position() {
+        let input = std::fs::read_to_string("input_test.txt").unwrap();
+        let input = input.replace("move 2 from 2 to 1", "move 1 from 9 to 1");
+        let err = Day05::parse_str(&input).err().unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let err = &errors.errors()[0];
+        assert_eq!(err.kind(), ParseErrorKind::InvalidValue);
+        assert!(err.description().starts_with("no stack 9"), "{}", err);
+        assert_eq!((err.line(), err.column()), (Some(8), Some(13)));
+    }
+
+    #[test]
+    fn malformed_crate_is_reported_with_position() {
+        let input = std::fs::read_to_string("input_test.txt").unwrap();
+        let input = input.replace("[Z] [M] [P]", "[Z] [M] [p]");
+        let err = Day05::parse_str(&input).err().unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let err = &errors.errors()[0];
+        assert_eq!(err.kind(), ParseErrorKind::UnexpectedToken);
+        assert_eq!((err.line(), err.column()), (Some(3), Some(10)));
+    }
+}
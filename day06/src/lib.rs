@@ -0,0 +1,188 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+    io::BufRead,
+    num::NonZeroUsize,
+};
+
+use aoc2022::{
+    detect::share_of_lines, Detect, Diagnostics, Generate, Param, Parameter, Params, Solution,
+};
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+pub struct Day06;
+
+fn decr_or_del(counts: &mut HashMap<u8, usize>, key: &u8) {
+    let counter = counts.get_mut(key).unwrap();
+    if *counter == 1 {
+        counts.remove(key);
+    } else {
+        *counter -= 1;
+    }
+}
+
+fn add_or_incr(counts: &mut HashMap<u8, usize>, key: &u8) {
+    *counts.entry(*key).or_insert(0) += 1;
+}
+
+/// The number of characters up to and including the first `n` distinct ones in a row, if
+/// there are any.
+fn compute(data: &[u8], n: usize) -> Option<usize> {
+    let mut window: VecDeque<u8> = VecDeque::with_capacity(n);
+
+    let mut count: usize = 0;
+    let mut counts = HashMap::new();
+    for &ch in data {
+        count += 1;
+
+        // Ensure we don't exceed our window size (make room for new entry if needed).
+        if window.len() == n {
+            let old_ch = window.pop_front().unwrap();
+            decr_or_del(&mut counts, &old_ch);
+        }
+
+        // Push new entry to window and count tracker.
+        window.push_back(ch);
+        add_or_incr(&mut counts, &ch);
+
+        // If window has reached saturation, check if all items are unique.
+        if window.len() == n && counts.values().all(|v| *v == 1) {
+            return Some(count);
+        }
+    }
+
+    None
+}
+
+/// Finds the marker of `n` distinct characters, failing if the datastream has none.
+fn find_marker(data: &[u8], n: usize) -> Result<usize, Box<dyn Error>> {
+    compute(data, n).ok_or_else(|| format!("no marker of {} distinct characters", n).into())
+}
+
+const PACKET_MARKER: Parameter<NonZeroUsize> = Parameter::new(
+    "packet_marker",
+    "4",
+    "distinct characters that mark the start of a packet, in part 1",
+);
+
+const MESSAGE_MARKER: Parameter<NonZeroUsize> = Parameter::new(
+    "message_marker",
+    "14",
+    "distinct characters that mark the start of a message, in part 2",
+);
+
+impl Solution for Day06 {
+    type Input = Vec<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const PARAMETERS: &'static [&'static dyn Param] = &[&PACKET_MARKER, &MESSAGE_MARKER];
+
+    fn parse<R: BufRead>(
+        mut reader: R,
+        _diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        let mut data = vec![];
+        reader.read_to_end(&mut data)?;
+        // The line break is not part of the datastream, and could complete a marker.
+        while let Some(b'\n' | b'\r') = data.last() {
+            data.pop();
+        }
+        Ok(data)
+    }
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        find_marker(input, params.get(&PACKET_MARKER)?.get())
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        find_marker(input, params.get(&MESSAGE_MARKER)?.get())
+    }
+}
+
+/// Generates a datastream of at least `size` characters.
+///
+/// Up to a random point only thirteen different letters are used, so that the
+/// start-of-message marker that follows cannot show up any earlier.
+impl Generate for Day06 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        let letter = |rng: &mut dyn RngCore, count: u8| (b'a' + rng.gen_range(0..count)) as char;
+        let start = rng.gen_range(0..=size.saturating_sub(14));
+
+        let mut input = (0..start).map(|_| letter(rng, 13)).collect::<String>();
+        let mut marker = ('a'..='z').collect::<Vec<_>>();
+        marker.shuffle(rng);
+        input.extend(&marker[..14]);
+        while input.len() < size {
+            input.push(letter(rng, 26));
+        }
+        input.push('\n');
+        input
+    }
+}
+
+/// The datastream is a single line of lower case letters.
+impl Detect for Day06 {
+    fn detect(&self, sample: &str) -> f64 {
+        let lines = sample.lines().filter(|line| !line.is_empty()).count();
+        let single = if lines == 1 { 1.0 } else { 0.5 };
+        single
+            * share_of_lines(sample, |line| {
+                line.bytes().all(|byte| byte.is_ascii_lowercase())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Checks every window from scratch.
+    fn naive_compute(data: &[u8], n: usize) -> Option<usize> {
+        data.windows(n)
+            .position(|window| window.iter().collect::<HashSet<_>>().len() == n)
+            .map(|start| start + n)
+    }
+
+    #[test]
+    fn start_of_packet_marker() {
+        let datastream = Day06::parse_str("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap();
+        assert_eq!(Day06::part1(&datastream).unwrap(), 5);
+    }
+
+    #[test]
+    fn start_of_message_marker() {
+        let datastream = Day06::parse_str("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap();
+        assert_eq!(Day06::part2(&datastream).unwrap(), 23);
+    }
+
+    #[test]
+    fn line_break_is_not_part_of_a_marker() {
+        for input in ["aaaa\n", "aabc\n", "aabc\r\n", ""] {
+            let datastream = Day06::parse_str(input).unwrap();
+            assert!(Day06::part1(&datastream).is_err(), "{:?}", input);
+        }
+        let datastream = Day06::parse_str("aabcd\n").unwrap();
+        assert_eq!(Day06::part1(&datastream).unwrap(), 5);
+    }
+
+    proptest! {
+        #[test]
+        fn markers_match_naive_markers(
+            // Small alphabets make markers rare, so that windows slide a long way.
+            data in (1..=20_u8).prop_flat_map(|letters| {
+                prop::collection::vec(b'a'..b'a' + letters, 0..80)
+            }),
+            n in 1..=14_usize,
+            line_break in prop::sample::select(vec!["", "\n", "\r\n"]),
+        ) {
+            let text = String::from_utf8(data.clone()).unwrap() + line_break;
+            let datastream = Day06::parse_str(&text).unwrap();
+            prop_assert_eq!(compute(&datastream, n), naive_compute(&data, n));
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use aoc2022::{
+    detect::{is_digits, share_of_lines},
+    parser::{
+        alt, label, map, number, optional, pair, parse_line, preceded, rest, separated_pair, tag,
+        word,
+    },
+    Detect, Diagnostics, Generate, Param, Parameter, Params, ParseError, ParseErrorKind, Solution,
+};
+use rand::{Rng, RngCore};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    io::BufRead,
+};
+
+pub struct Day07;
+
+/// A line of terminal output: a command with its argument, or a listed file size.
+enum Line<'a> {
+    Command((&'a str, Option<&'a str>)),
+    Entry((Option<usize>, &'a str)),
+}
+
+pub struct FileSystem {
+    sizes: HashMap<String, usize>,
+    used_size: usize,
+}
+
+fn parse_terminal_line(line_number: usize, line: &str) -> Result<Line<'_>, ParseError> {
+    let command = preceded(
+        tag("$ "),
+        pair(word(), optional(preceded(tag(" "), rest()))),
+    );
+    let size = alt(map(tag("dir"), |_| None), map(number(), Some));
+    let entry = separated_pair(size, tag(" "), rest());
+    let parser = label(
+        alt(map(command, Line::Command), map(entry, Line::Entry)),
+        "a command or a directory listing entry",
+    );
+
+    parse_line(line_number, line, parser)
+}
+
+const SMALL_DIRECTORY: Parameter<usize> = Parameter::new(
+    "small_directory",
+    "100000",
+    "directories smaller than this are added up in part 1",
+);
+
+const DISK_SIZE: Parameter<usize> = Parameter::new("disk_size", "70000000", "size of the disk");
+
+const REQUIRED_SPACE: Parameter<usize> = Parameter::new(
+    "required_space",
+    "30000000",
+    "free space needed for the update in part 2",
+);
+
+impl Solution for Day07 {
+    type Input = FileSystem;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const PARAMETERS: &'static [&'static dyn Param] =
+        &[&SMALL_DIRECTORY, &DISK_SIZE, &REQUIRED_SPACE];
+
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        let mut path: Vec<String> = vec![];
+        let mut sizes: HashMap<String, usize> = HashMap::new();
+        let mut used_size: usize = 0;
+        for (index, line) in reader.lines().enumerate() {
+            let input = &line?;
+            if input.is_empty() {
+                continue;
+            }
+            let unknown_command = |op: &str| {
+                ParseError::new(ParseErrorKind::UnknownCommand, format!("`{}`", op))
+                    .at(index + 1, input)
+                    .at_offset("$ ".len())
+            };
+            match diagnostics.check(parse_terminal_line(index + 1, input)) {
+                Some(Line::Command((op, Some(arg)))) => {
+                    if op == "cd" {
+                        if arg.trim() == ".." {
+                            path.pop();
+                        } else if arg.trim() == "/" {
+                            path.clear();
+                        } else {
+                            path.push(arg.trim().to_string());
+                        }
+                    } else {
+                        diagnostics.report(unknown_command(op));
+                    }
+                }
+                Some(Line::Command((op, None))) => {
+                    if op == "ls" {
+                        // don't care, really...
+                    } else {
+                        diagnostics.report(unknown_command(op));
+                    }
+                }
+                Some(Line::Entry((Some(size), _))) => {
+                    used_size += size;
+                    for i in 0..path.len() {
+                        let pwd = path
+                            .iter()
+                            .take(path.len() - i)
+                            .cloned()
+                            .collect::<Vec<_>>()
+                            .join("/");
+                        *sizes.entry(pwd).or_insert(0) += size;
+                    }
+                }
+                Some(Line::Entry((None, _))) | None => {
+                    // directories don't care, errors are reported
+                }
+            }
+        }
+
+        Ok(FileSystem { sizes, used_size })
+    }
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        let small_directory = params.get(&SMALL_DIRECTORY)?;
+        Ok(input
+            .sizes
+            .values()
+            .filter(|size| **size < small_directory)
+            .sum())
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        let current_free = params
+            .get(&DISK_SIZE)?
+            .checked_sub(input.used_size)
+            .ok_or("the files do not fit on the disk")?;
+        let need_to_free = params
+            .get(&REQUIRED_SPACE)?
+            .checked_sub(current_free)
+            .ok_or("there is enough free space already")?;
+        let mut best_size = usize::MAX;
+        for size in input.sizes.values() {
+            if *size > need_to_free && *size < best_size {
+                best_size = *size;
+            }
+        }
+        Ok(best_size)
+    }
+}
+
+/// Generates a terminal session exploring a tree of `size` directories.
+///
+/// New directories are nested in the previous one half of the time, which makes for deep
+/// trees, and the files add up to between 45 and 65 million bytes, so that some directory
+/// has to be deleted to free up enough space.
+impl Generate for Day07 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        let count = size.max(1);
+        let name = |rng: &mut dyn RngCore, taken: &mut HashSet<String>| loop {
+            let name = (0..rng.gen_range(1..=8))
+                .map(|_| (b'a' + rng.gen_range(0..26)) as char)
+                .collect::<String>();
+            if taken.insert(name.clone()) {
+                return name;
+            }
+        };
+
+        let mut names = vec![String::new()];
+        let mut children = vec![vec![]; count];
+        let mut taken = vec![HashSet::new(); count];
+        for dir in 1..count {
+            let parent = if rng.gen_bool(0.5) {
+                dir - 1
+            } else {
+                rng.gen_range(0..dir)
+            };
+            names.push(name(rng, &mut taken[parent]));
+            children[parent].push(dir);
+        }
+        let files = (0..count)
+            .map(|dir| {
+                let count = rng.gen_range(if dir == 0 { 1 } else { 0 }..=4);
+                (0..count)
+                    .map(|_| (name(rng, &mut taken[dir]), rng.gen_range(1..=1000)))
+                    .collect::<Vec<(String, usize)>>()
+            })
+            .collect::<Vec<_>>();
+        let weights = files
+            .iter()
+            .flatten()
+            .map(|(_, weight)| weight)
+            .sum::<usize>();
+        let used = rng.gen_range(45_000_000..=65_000_000);
+
+        let mut input = "$ cd /\n".to_string();
+        let mut stack = vec![Some(0)];
+        while let Some(dir) = stack.pop() {
+            let Some(dir) = dir else {
+                input.push_str("$ cd ..\n");
+                continue;
+            };
+            if dir != 0 {
+                input.push_str(&format!("$ cd {}\n", names[dir]));
+            }
+            input.push_str("$ ls\n");
+            for &child in &children[dir] {
+                input.push_str(&format!("dir {}\n", names[child]));
+            }
+            for (name, weight) in &files[dir] {
+                input.push_str(&format!("{} {}\n", (weight * used / weights).max(1), name));
+            }
+            for &child in children[dir].iter().rev() {
+                stack.push(None);
+                stack.push(Some(child));
+            }
+        }
+        input
+    }
+}
+
+/// A terminal session of `cd` and `ls` commands and their output.
+impl Detect for Day07 {
+    fn detect(&self, sample: &str) -> f64 {
+        share_of_lines(sample, |line| {
+            line.starts_with("$ cd ")
+                || line == "$ ls"
+                || line.starts_with("dir ")
+                || line
+                    .split_once(' ')
+                    .is_some_and(|(size, name)| is_digits(size) && !name.is_empty())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc2022::ParseErrors;
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let file_system = Day07::parse_file("input_test.txt").unwrap();
+        assert_eq!(file_system.used_size, 48381165);
+        assert_eq!(file_system.sizes["a/e"], 584);
+        assert_eq!(Day07::part1(&file_system).unwrap(), 95437);
+        assert_eq!(Day07::part2(&file_system).unwrap(), 24933642);
+    }
+
+    #[test]
+    fn unknown_command_is_reported_with_position() {
+        let err = Day07::parse_str("$ cd /\n$ rm -rf a\n$ ls\n")
+            .err()
+            .unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let err = &errors.errors()[0];
+        assert_eq!(err.kind(), ParseErrorKind::UnknownCommand);
+        assert_eq!((err.line(), err.column()), (Some(2), Some(3)));
+    }
+
+    #[test]
+    fn malformed_entry_is_reported_with_position() {
+        let err = Day07::parse_str("$ ls\n12x a.txt\n").err().unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let err = &errors.errors()[0];
+        assert_eq!(err.kind(), ParseErrorKind::UnexpectedToken);
+        assert_eq!((err.line(), err.column()), (Some(2), Some(3)));
+    }
+}
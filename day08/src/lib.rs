@@ -0,0 +1,261 @@
+#![cfg_attr(feature = "nightly", feature(test))]
+#[cfg(feature = "nightly")]
+extern crate test;
+
+use std::{error::Error, io::BufRead};
+
+use aoc2022::{
+    detect::{is_digits, share_of_lines},
+    Detect, Diagnostics, Generate, Grid, Params, ParseError, ParseErrorKind, Solution,
+};
+use rand::{Rng, RngCore};
+
+pub struct Day08;
+
+fn get_outside_in_heights(tree_line: &mut dyn Iterator<Item = &usize>) -> Vec<usize> {
+    tree_line
+        .scan(0_usize, move |height, &tree_height| {
+            let current_height = *height;
+            *height = (*height).max(tree_height);
+            Some(current_height)
+        })
+        .collect()
+}
+
+fn get_inside_out_heights(tree_line: &mut dyn Iterator<Item = &usize>) -> Vec<usize> {
+    tree_line
+        .scan([0_usize; 11], |heights, &tree_height| {
+            let value = heights[tree_height];
+            heights.iter_mut().enumerate().for_each(|(index, height)| {
+                if index > tree_height {
+                    *height += 1;
+                } else {
+                    *height = 1
+                }
+            });
+
+            Some(value)
+        })
+        .collect()
+}
+
+fn cross_traverse_heights<'a>(
+    heights: &'a Grid<usize>,
+    height_line_builder: fn(&mut dyn Iterator<Item = &'a usize>) -> Vec<usize>,
+    height_merger: fn(usize, usize) -> usize,
+) -> Grid<usize> {
+    let mut result = Grid::new(heights.width(), heights.height(), 0);
+
+    for row in 0..heights.height() {
+        let left_to_right_heights = height_line_builder(&mut heights.row(row)).into_iter();
+        let right_to_left_heights = height_line_builder(&mut heights.row(row).rev())
+            .into_iter()
+            .rev();
+        let row_heights = left_to_right_heights
+            .zip(right_to_left_heights)
+            .map(|(a, b)| height_merger(a, b));
+
+        for (col, height) in row_heights.enumerate() {
+            result[(row, col)] = height;
+        }
+    }
+
+    for col in 0..heights.width() {
+        let top_to_bottom_heights = height_line_builder(&mut heights.column(col)).into_iter();
+        let bottom_to_top_heights = height_line_builder(&mut heights.column(col).rev())
+            .into_iter()
+            .rev();
+        let col_heights = top_to_bottom_heights
+            .zip(bottom_to_top_heights)
+            .map(|(a, b)| height_merger(a, b));
+
+        for (row, height) in col_heights.enumerate() {
+            result[(row, col)] = height_merger(result[(row, col)], height);
+        }
+    }
+
+    result
+}
+
+fn count_visible_trees(trees: &Grid<usize>) -> usize {
+    let visible_heights = cross_traverse_heights(trees, get_outside_in_heights, usize::min);
+
+    visible_heights
+        .iter()
+        .zip(trees.iter())
+        .filter(|(view_height, tree_height)| tree_height > view_height)
+        .count()
+}
+
+fn compute_scenic_score(trees: &Grid<usize>) -> Grid<usize> {
+    cross_traverse_heights(trees, get_inside_out_heights, |a, b| a * b)
+}
+
+impl Solution for Day08 {
+    type Input = Grid<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        Grid::parse(reader, diagnostics, |ch| match ch.to_digit(10) {
+            Some(height) => Ok(height as usize + 1),
+            None => Err(ParseError::new(
+                ParseErrorKind::UnexpectedToken,
+                format!("expected a tree height from 0 to 9, found `{}`", ch),
+            )),
+        })
+    }
+
+    fn part1_with(input: &Self::Input, _params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        Ok(count_visible_trees(input))
+    }
+
+    fn part2_with(input: &Self::Input, _params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        let scenic_score = compute_scenic_score(input);
+        Ok(*scenic_score.iter().max().unwrap())
+    }
+}
+
+/// Generates a square forest of `size` by `size` trees.
+impl Generate for Day08 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        let size = size.max(1);
+        (0..size)
+            .map(|_| {
+                let mut row = (0..size)
+                    .map(|_| char::from_digit(rng.gen_range(0..10), 10).unwrap())
+                    .collect::<String>();
+                row.push('\n');
+                row
+            })
+            .collect()
+    }
+}
+
+/// The forest is a rectangle of digits.
+impl Detect for Day08 {
+    fn detect(&self, sample: &str) -> f64 {
+        let width = sample.lines().next().map_or(0, str::len);
+        let rectangle = if sample.lines().all(|line| line.len() == width) {
+            1.0
+        } else {
+            0.5
+        };
+        rectangle * share_of_lines(sample, is_digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// The trees seen from `position` when looking towards `step`, up to the edge.
+    fn naive_view(
+        trees: &Grid<usize>,
+        (row, col): (usize, usize),
+        step: (isize, isize),
+    ) -> Vec<usize> {
+        trees.walk((row, col), step).skip(1).copied().collect()
+    }
+
+    const STEPS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+    fn naive_count_visible_trees(trees: &Grid<usize>) -> usize {
+        trees
+            .positions()
+            .filter(|&position| {
+                STEPS.iter().any(|&step| {
+                    naive_view(trees, position, step)
+                        .iter()
+                        .all(|&other| other < trees[position])
+                })
+            })
+            .count()
+    }
+
+    fn naive_scenic_score(trees: &Grid<usize>) -> Grid<usize> {
+        let scores = trees.positions().map(|position| {
+            STEPS
+                .iter()
+                .map(|&step| {
+                    let view = naive_view(trees, position, step);
+                    match view.iter().position(|&other| other >= trees[position]) {
+                        Some(blocked) => blocked + 1,
+                        None => view.len(),
+                    }
+                })
+                .product()
+        });
+        Grid::from_cells(trees.width(), scores.collect())
+    }
+
+    fn forest() -> impl Strategy<Value = Grid<usize>> {
+        (1..10_usize, 1..10_usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(1..=10_usize, width * height)
+                .prop_map(move |cells| Grid::from_cells(width, cells))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn visibility_matches_naive_count(trees in forest()) {
+            prop_assert_eq!(count_visible_trees(&trees), naive_count_visible_trees(&trees));
+        }
+
+        #[test]
+        fn scenic_score_matches_naive_score(trees in forest()) {
+            prop_assert_eq!(compute_scenic_score(&trees), naive_scenic_score(&trees));
+        }
+    }
+
+    #[test]
+    fn correct_row_count() {
+        let trees = Day08::parse_file("input_test.txt").unwrap();
+        assert_eq!(trees.height(), 5);
+    }
+
+    #[test]
+    fn correct_col_count() {
+        let trees = Day08::parse_file("input_test.txt").unwrap();
+        assert_eq!(trees.width(), 5);
+    }
+
+    #[test]
+    fn correct_visibility_count() {
+        let trees = Day08::parse_file("input_test.txt").unwrap();
+        let visible_tree_count = count_visible_trees(&trees);
+        assert_eq!(visible_tree_count, 21);
+    }
+
+    #[test]
+    fn correct_scenic_score() {
+        let trees = Day08::parse_file("input_test.txt").unwrap();
+        let scenic_score = compute_scenic_score(&trees);
+        assert_eq!(scenic_score[(1, 2)], 4);
+        assert_eq!(scenic_score[(3, 2)], 8);
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn visible_tree_perf(b: &mut test::Bencher) {
+        let trees = Day08::parse_file("input_test.txt").unwrap();
+        b.iter(|| {
+            let visible_tree_count = count_visible_trees(&trees);
+        });
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn scenic_score_perf(b: &mut test::Bencher) {
+        let trees = Day08::parse_file("input_test.txt").unwrap();
+        b.iter(|| {
+            let scenic_score = compute_scenic_score(&trees);
+            let best_scenic_score = *scenic_score.iter().max().unwrap();
+        });
+    }
+}
@@ -0,0 +1,476 @@
+use std::{collections::HashSet, error::Error, fmt, io::BufRead, iter, num::NonZeroUsize};
+
+use aoc2022::{
+    detect::{is_digits, share_of_lines},
+    event, parse_number,
+    simulate::{fast_forward, run_to_end},
+    trace::Level,
+    vector::{Direction4, Vector2},
+    Detect, Diagnostics, Generate, Param, Parameter, Params, ParseError, ParseErrorKind, Part,
+    Simulate, Simulation, Solution, Stepwise,
+};
+use rand::{seq::SliceRandom, Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+pub struct Day09;
+
+type Position = Vector2<i32>;
+
+/// A rope whose head follows the moves one step at a time, dragging its knots along.
+#[derive(Clone, Debug)]
+pub struct Rope {
+    knots: Vec<Position>,
+    moves: Vec<(Position, usize)>,
+    /// The move in progress, and how many of its steps are done.
+    current: usize,
+    taken: usize,
+    visited_positions: HashSet<Position>,
+    /// The moves repeat every `period` moves.
+    period: usize,
+}
+
+/// The smallest `p` such that every move is `p` moves after the same one, which is the
+/// number of moves if there is no repetition.
+fn period<T>(moves: &[T]) -> usize
+where
+    T: PartialEq,
+{
+    // The length of the longest proper prefix that is also a suffix, for every prefix.
+    let mut borders = vec![0; moves.len()];
+    for i in 1..moves.len() {
+        let mut border = borders[i - 1];
+        while border > 0 && moves[i] != moves[border] {
+            border = borders[border - 1];
+        }
+        if moves[i] == moves[border] {
+            border += 1;
+        }
+        borders[i] = border;
+    }
+    moves.len() - borders.last().unwrap_or(&0)
+}
+
+/// Moves the first knot by `movement` and drags the others along.
+fn pull(knots: &mut [Position], movement: Position) {
+    knots[0] += movement;
+    for i in 0..knots.len() - 1 {
+        let head = knots[i];
+        let tail = &mut knots[i + 1];
+
+        if head.chebyshev_distance(*tail) > 1 {
+            *tail += (head - *tail).signum();
+        }
+    }
+}
+
+impl Rope {
+    pub fn new(moves: &[(Position, usize)], knot_count: usize) -> Self {
+        assert_ne!(knot_count, 0);
+
+        let knots = vec![Position::ZERO; knot_count];
+        let mut rope = Self {
+            visited_positions: HashSet::from([*knots.last().unwrap()]),
+            knots,
+            moves: moves.to_vec(),
+            current: 0,
+            taken: 0,
+            period: period(moves),
+        };
+        rope.finish_moves();
+        rope
+    }
+
+    fn head(&self) -> Position {
+        self.knots[0]
+    }
+
+    fn tail(&self) -> Position {
+        *self.knots.last().unwrap()
+    }
+
+    /// The number of positions the tail has visited.
+    pub fn visited(&self) -> usize {
+        self.visited_positions.len()
+    }
+
+    /// Moves on to the next move while all steps of the current one are done.
+    fn finish_moves(&mut self) {
+        while self
+            .moves
+            .get(self.current)
+            .is_some_and(|&(_, times)| self.taken == times)
+        {
+            event!(
+                Level::Debug,
+                "move",
+                head = format!("({})", self.head()),
+                tail = format!("({})", self.tail()),
+                visited = self.visited(),
+            );
+            self.current += 1;
+            self.taken = 0;
+        }
+    }
+}
+
+/// What changes while a [`Rope`] follows the moves.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RopeSnapshot {
+    pub knots: Vec<Position>,
+    /// The move in progress, and how many of its steps are done.
+    pub current: usize,
+    pub taken: usize,
+    /// The positions the tail visited, sorted.
+    pub visited_positions: Vec<Position>,
+}
+
+impl Simulation for Rope {
+    /// Where the knots are relative to the head, at the start of a repetition of the moves.
+    type Key = Vec<Position>;
+
+    /// Moves the head by one position.
+    fn step(&mut self) {
+        pull(&mut self.knots, self.moves[self.current].0);
+        self.visited_positions.insert(self.tail());
+        event!(
+            Level::Trace,
+            "step",
+            head = format!("({})", self.head()),
+            tail = format!("({})", self.tail()),
+        );
+        self.taken += 1;
+        self.finish_moves();
+    }
+
+    fn is_done(&self) -> bool {
+        self.current == self.moves.len()
+    }
+
+    fn key(&self) -> Self::Key {
+        let head = self.head();
+        self.knots.iter().map(|&knot| knot - head).collect()
+    }
+
+    /// The rope can only go on the same way once the moves start over.
+    fn is_checkpoint(&self) -> bool {
+        self.taken == 0 && self.current.is_multiple_of(self.period)
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        let later = self.moves[self.current..].iter().map(|&(_, times)| times);
+        Some(later.sum::<usize>() - self.taken)
+    }
+
+    /// Moves the rope along by the distance the head moved during the cycle, which the tail
+    /// follows by visiting the positions it visited during the cycle, shifted along.
+    fn fast_forward(&mut self, start: &Self, period: usize, cycles: usize) {
+        let shift = self.head() - start.head();
+        if shift != Position::ZERO {
+            let mut knots = start.knots.clone();
+            let cycle_positions = self.moves[start.current..]
+                .iter()
+                .flat_map(|&(movement, times)| iter::repeat_n(movement, times))
+                .skip(start.taken)
+                .take(period)
+                .map(|movement| {
+                    pull(&mut knots, movement);
+                    *knots.last().unwrap()
+                })
+                .collect::<Vec<_>>();
+            for cycle in 1..=cycles as i32 {
+                self.visited_positions.extend(
+                    cycle_positions
+                        .iter()
+                        .map(|&position| position + shift * cycle),
+                );
+            }
+        }
+        for knot in &mut self.knots {
+            *knot += shift * cycles as i32;
+        }
+        self.current += (self.current - start.current) * cycles;
+    }
+}
+
+impl Stepwise for Rope {
+    type Snapshot = RopeSnapshot;
+
+    fn values(&self) -> Vec<(String, i64)> {
+        let (head, tail) = (self.head(), self.tail());
+        [
+            ("moves", self.current as i64),
+            ("head_x", head.x.into()),
+            ("head_y", head.y.into()),
+            ("tail_x", tail.x.into()),
+            ("tail_y", tail.y.into()),
+            ("visited", self.visited() as i64),
+        ]
+        .map(|(name, value)| (name.to_string(), value))
+        .to_vec()
+    }
+
+    fn snapshot(&self) -> RopeSnapshot {
+        let mut visited_positions = self.visited_positions.iter().copied().collect::<Vec<_>>();
+        visited_positions.sort();
+        RopeSnapshot {
+            knots: self.knots.clone(),
+            current: self.current,
+            taken: self.taken,
+            visited_positions,
+        }
+    }
+
+    fn restore(&mut self, snapshot: RopeSnapshot) -> Result<(), Box<dyn Error>> {
+        if snapshot.knots.len() != self.knots.len() {
+            return Err(format!(
+                "the snapshot has {} knots, expected {}",
+                snapshot.knots.len(),
+                self.knots.len()
+            )
+            .into());
+        }
+        let steps = match self.moves.get(snapshot.current) {
+            Some(&(_, times)) => times,
+            None if snapshot.current == self.moves.len() => 0,
+            None => {
+                return Err(format!(
+                    "the snapshot is at move {}, but there are only {}",
+                    snapshot.current,
+                    self.moves.len()
+                )
+                .into())
+            }
+        };
+        if snapshot.taken > steps {
+            return Err(format!(
+                "the snapshot took {} steps of a move of {}",
+                snapshot.taken, steps
+            )
+            .into());
+        }
+        self.knots = snapshot.knots;
+        self.current = snapshot.current;
+        self.taken = snapshot.taken;
+        self.visited_positions = snapshot.visited_positions.into_iter().collect();
+        Ok(())
+    }
+}
+
+/// Draws the area around the rope like the puzzle does: the head is `H`, the tail of a
+/// short rope is `T` and the knots of a long one are numbered, `s` is the start and `#`
+/// marks positions the tail visited.
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const MARGIN: i32 = 2;
+        let (min, max) = self
+            .knots
+            .iter()
+            .fold((self.head(), self.head()), |(min, max), knot| {
+                (
+                    Position::new(min.x.min(knot.x), min.y.min(knot.y)),
+                    Position::new(max.x.max(knot.x), max.y.max(knot.y)),
+                )
+            });
+        for y in (min.y - MARGIN..=max.y + MARGIN).rev() {
+            for x in min.x - MARGIN..=max.x + MARGIN {
+                let position = Position::new(x, y);
+                let cell = match self.knots.iter().position(|&knot| knot == position) {
+                    Some(0) => 'H',
+                    Some(_) if self.knots.len() == 2 => 'T',
+                    Some(index) => char::from_digit(index as u32 % 10, 10).unwrap(),
+                    None if position == Position::ZERO => 's',
+                    None if self.visited_positions.contains(&position) => '#',
+                    None => '.',
+                };
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        write!(
+            f,
+            "head at ({}), tail at ({}), {} positions visited",
+            self.head(),
+            self.tail(),
+            self.visited()
+        )?;
+        match self.moves.get(self.current) {
+            Some(&(movement, times)) => write!(
+                f,
+                "\nmove {} of {}: ({}) {} times, {} done",
+                self.current + 1,
+                self.moves.len(),
+                movement,
+                times,
+                self.taken
+            ),
+            None => write!(f, "\nall {} moves done", self.moves.len()),
+        }
+    }
+}
+
+fn simulate_moves(moves: &[(Position, usize)], knot_count: usize) -> usize {
+    let mut rope = Rope::new(moves, knot_count);
+    if rope.period < moves.len() {
+        fast_forward(&mut rope, usize::MAX);
+    } else {
+        run_to_end(&mut rope);
+    }
+    rope.visited()
+}
+
+fn parse_move(line_number: usize, line: &str) -> Result<(Position, usize), ParseError> {
+    let (direction, count) = line.split_once(' ').ok_or_else(|| {
+        ParseError::new(ParseErrorKind::InvalidLine, "expected direction and count")
+            .at(line_number, line)
+    })?;
+    let head_movement = match direction {
+        "U" => Direction4::Up,
+        "D" => Direction4::Down,
+        "L" => Direction4::Left,
+        "R" => Direction4::Right,
+        _ => {
+            return Err(ParseError::new(
+                ParseErrorKind::UnexpectedToken,
+                format!("expected one of `U`, `D`, `L`, `R`, found `{}`", direction),
+            )
+            .at(line_number, line)
+            .at_column(1))
+        }
+    };
+    let times = parse_number(count)
+        .map_err(|err| err.at(line_number, line).at_offset(direction.len() + 1))?;
+    Ok((head_movement.to_vector(), times))
+}
+
+const ROPE_KNOTS: Parameter<NonZeroUsize> =
+    Parameter::new("rope_knots", "2", "knots of the rope in part 1");
+
+const LONG_ROPE_KNOTS: Parameter<NonZeroUsize> =
+    Parameter::new("long_rope_knots", "10", "knots of the rope in part 2");
+
+impl Solution for Day09 {
+    /// Head movements as direction and number of steps.
+    type Input = Vec<(Position, usize)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const PARAMETERS: &'static [&'static dyn Param] = &[&ROPE_KNOTS, &LONG_ROPE_KNOTS];
+
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        let mut moves = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if !line.is_empty() {
+                moves.extend(diagnostics.check(parse_move(index + 1, &line)));
+            }
+        }
+
+        Ok(moves)
+    }
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        Ok(simulate_moves(input, params.get(&ROPE_KNOTS)?.get()))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        Ok(simulate_moves(input, params.get(&LONG_ROPE_KNOTS)?.get()))
+    }
+}
+
+impl Simulate for Day09 {
+    type State = Rope;
+
+    fn start(input: &Self::Input, part: Part, params: &Params) -> Result<Rope, Box<dyn Error>> {
+        let knots = match part {
+            Part::One => params.get(&ROPE_KNOTS)?,
+            Part::Two => params.get(&LONG_ROPE_KNOTS)?,
+        };
+        Ok(Rope::new(input, knots.get()))
+    }
+}
+
+/// Generates `size` moves of the head.
+impl Generate for Day09 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let direction = *['U', 'D', 'L', 'R'].choose(rng).unwrap();
+                format!("{} {}\n", direction, rng.gen_range(1..=20))
+            })
+            .collect()
+    }
+}
+
+/// Every line is a move like `R 4`.
+impl Detect for Day09 {
+    fn detect(&self, sample: &str) -> f64 {
+        share_of_lines(sample, |line| {
+            line.split_once(' ').is_some_and(|(direction, steps)| {
+                ["U", "D", "L", "R"].contains(&direction) && is_digits(steps)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc2022::ParseErrors;
+
+    use super::*;
+
+    #[test]
+    fn single_knot() {
+        let moves = Day09::parse_file("input_test.txt").unwrap();
+        assert_eq!(simulate_moves(&moves, 2), 13);
+    }
+
+    #[test]
+    fn many_knots() {
+        let moves = Day09::parse_file("input_test.txt").unwrap();
+        assert_eq!(simulate_moves(&moves, 10), 1);
+        let moves = Day09::parse_file("input_test2.txt").unwrap();
+        assert_eq!(simulate_moves(&moves, 10), 36);
+    }
+
+    #[test]
+    fn repeating_moves_are_skipped() {
+        assert_eq!(period(&[1, 2, 1, 2, 1]), 2);
+        assert_eq!(period(&[1, 1, 2]), 3);
+        assert_eq!(period::<u8>(&[]), 0);
+
+        for (pattern, repeats) in [("R 4\nU 4\nL 3\nD 1\n", 50), ("R 5\nL 5\nU 1\nD 1\n", 30)] {
+            let moves = Day09::parse_str(&pattern.repeat(repeats)).unwrap();
+            for knots in [2, 10] {
+                let mut stepped = Rope::new(&moves, knots);
+                let mut skipped = stepped.clone();
+                run_to_end(&mut stepped);
+                fast_forward(&mut skipped, usize::MAX);
+                assert_eq!(skipped.snapshot(), stepped.snapshot());
+            }
+        }
+    }
+
+    #[test]
+    fn long_moves_without_repetition() {
+        let moves = Day09::parse_str(&Day09.generate_seeded(1, 50_000)).unwrap();
+        assert_eq!(period(&moves), moves.len());
+        // Only the start is a checkpoint, so there are no keys to compute along the way.
+        let mut stepped = Rope::new(&moves, 10);
+        let mut skipped = stepped.clone();
+        run_to_end(&mut stepped);
+        fast_forward(&mut skipped, usize::MAX);
+        assert_eq!(skipped.snapshot(), stepped.snapshot());
+    }
+
+    #[test]
+    fn invalid_direction_is_reported_with_position() {
+        let err = Day09::parse_str("R 4\nX 2\n").err().unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let err = &errors.errors()[0];
+        assert_eq!(err.kind(), ParseErrorKind::UnexpectedToken);
+        assert_eq!((err.line(), err.column()), (Some(2), Some(1)));
+    }
+}
@@ -0,0 +1,387 @@
+use std::{error::Error, fmt, io::BufRead, num::NonZeroUsize};
+
+use aoc2022::{
+    detect::{is_digits, share_of_lines},
+    event,
+    params::List,
+    parse_number,
+    simulate::{run_steps, run_to_end},
+    trace::Level,
+    Detect, Diagnostics, Generate, Grid, Param, Parameter, Params, ParseError, ParseErrorKind,
+    Part, Simulate, Simulation, Solution, Stepwise,
+};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+pub struct Day10;
+
+#[derive(Copy, Clone, Debug)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+/// The CPU running a program one cycle at a time, while the CRT draws a pixel per cycle.
+#[derive(Clone, Debug)]
+pub struct Cpu {
+    program: Vec<Instruction>,
+    /// The instruction in progress, and whether its first cycle is done.
+    current: usize,
+    started: bool,
+    x: i32,
+    /// The cycle in progress, starting at 1.
+    cycle: usize,
+    width: usize,
+    pixels: Vec<char>,
+}
+
+impl Cpu {
+    pub fn new(program: &[Instruction], width: usize) -> Self {
+        Self {
+            program: program.to_vec(),
+            current: 0,
+            started: false,
+            x: 1,
+            cycle: 1,
+            width,
+            pixels: vec![],
+        }
+    }
+
+    /// The signal strength during the current cycle.
+    pub fn signal(&self) -> i32 {
+        self.cycle as i32 * self.x
+    }
+
+    /// The rows the CRT drew so far, where the pixels of a row the program stopped in the
+    /// middle of stay dark.
+    pub fn screen(&self) -> Grid<char> {
+        let mut pixels = self.pixels.clone();
+        pixels.resize(pixels.len().next_multiple_of(self.width), '.');
+        Grid::from_cells(self.width, pixels)
+    }
+}
+
+/// What changes while a [`Cpu`] runs its program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CpuSnapshot {
+    /// The instruction in progress, and whether its first cycle is done.
+    pub current: usize,
+    pub started: bool,
+    pub x: i32,
+    pub cycle: usize,
+    /// The pixels drawn so far, row after row.
+    pub pixels: String,
+}
+
+impl Simulation for Cpu {
+    /// The instruction in progress, which never repeats.
+    type Key = (usize, bool);
+
+    /// Runs a cycle.
+    fn step(&mut self) {
+        let instruction = self.program[self.current];
+        if !self.started {
+            event!(
+                Level::Trace,
+                "execute",
+                instruction = format!("{:?}", instruction),
+                x = self.x
+            );
+        }
+        let column = ((self.cycle - 1) % self.width) as i32;
+        self.pixels
+            .push(if (self.x - 1..=self.x + 1).contains(&column) {
+                '#'
+            } else {
+                '.'
+            });
+        match instruction {
+            Instruction::Addx(_) if !self.started => self.started = true,
+            Instruction::Addx(value) => {
+                self.x += value;
+                self.started = false;
+                self.current += 1;
+            }
+            Instruction::Noop => self.current += 1,
+        }
+        self.cycle += 1;
+    }
+
+    fn is_done(&self) -> bool {
+        self.current == self.program.len()
+    }
+
+    fn key(&self) -> Self::Key {
+        (self.current, self.started)
+    }
+}
+
+impl Stepwise for Cpu {
+    type Snapshot = CpuSnapshot;
+
+    fn values(&self) -> Vec<(String, i64)> {
+        vec![
+            ("cycle".to_string(), self.cycle as i64),
+            ("x".to_string(), self.x.into()),
+            ("signal".to_string(), self.signal().into()),
+        ]
+    }
+
+    fn snapshot(&self) -> CpuSnapshot {
+        CpuSnapshot {
+            current: self.current,
+            started: self.started,
+            x: self.x,
+            cycle: self.cycle,
+            pixels: self.pixels.iter().collect(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: CpuSnapshot) -> Result<(), Box<dyn Error>> {
+        if snapshot.current > self.program.len() {
+            return Err(format!(
+                "the snapshot is at instruction {}, but there are only {}",
+                snapshot.current,
+                self.program.len()
+            )
+            .into());
+        }
+        if snapshot.cycle == 0 || snapshot.pixels.chars().count() != snapshot.cycle - 1 {
+            return Err(format!(
+                "the snapshot drew {} pixels before cycle {}",
+                snapshot.pixels.chars().count(),
+                snapshot.cycle
+            )
+            .into());
+        }
+        self.current = snapshot.current;
+        self.started = snapshot.started;
+        self.x = snapshot.x;
+        self.cycle = snapshot.cycle;
+        self.pixels = snapshot.pixels.chars().collect();
+        Ok(())
+    }
+}
+
+/// Shows the registers, the instruction in progress and what the CRT drew so far.
+impl fmt::Display for Cpu {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "during cycle {}: x = {}, signal strength {}",
+            self.cycle,
+            self.x,
+            self.signal()
+        )?;
+        match self.program.get(self.current) {
+            Some(Instruction::Noop) => write!(f, ", executing noop")?,
+            Some(Instruction::Addx(value)) => write!(
+                f,
+                ", executing addx {} (cycle {} of 2)",
+                value,
+                usize::from(self.started) + 1
+            )?,
+            None => write!(f, ", the program is done")?,
+        }
+        for row in self.pixels.chunks(self.width) {
+            write!(f, "\n{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_instruction(line_number: usize, inst: &str) -> Result<Instruction, ParseError> {
+    if inst == "noop" {
+        Ok(Instruction::Noop)
+    } else if let Some(value) = inst.strip_prefix("addx ") {
+        let value = parse_number(value)
+            .map_err(|err| err.at(line_number, inst).at_offset("addx ".len()))?;
+        Ok(Instruction::Addx(value))
+    } else {
+        Err(ParseError::new(
+            ParseErrorKind::UnknownCommand,
+            format!("`{}`", inst.split(' ').next().unwrap_or_default()),
+        )
+        .at(line_number, inst)
+        .at_column(1))
+    }
+}
+
+const SIGNAL_CYCLES: Parameter<List<NonZeroUsize>> = Parameter::new(
+    "signal_cycles",
+    "20,60,100,140,180,220",
+    "cycles during which the signal strength is added up in part 1",
+);
+
+const CRT_WIDTH: Parameter<NonZeroUsize> =
+    Parameter::new("crt_width", "40", "pixels in a row of the CRT in part 2");
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    type Part1 = i32;
+    /// The rendered CRT image, one line per row.
+    type Part2 = Grid<char>;
+
+    const PARAMETERS: &'static [&'static dyn Param] = &[&SIGNAL_CYCLES, &CRT_WIDTH];
+
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        let mut instructions = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let inst = line?;
+            if !inst.is_empty() {
+                instructions.extend(diagnostics.check(parse_instruction(index + 1, &inst)));
+            }
+        }
+        Ok(instructions)
+    }
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        let mut cycles = params
+            .get(&SIGNAL_CYCLES)?
+            .0
+            .into_iter()
+            .map(NonZeroUsize::get)
+            .collect::<Vec<_>>();
+        // The signal is sampled in a single run of the program.
+        cycles.sort_unstable();
+        cycles.dedup();
+        let mut cpu = Self::start(input, Part::One, params)?;
+        let mut strengths = 0;
+        for cycle in cycles {
+            let steps = cycle - cpu.cycle;
+            run_steps(&mut cpu, steps);
+            // The program stopped before the cycle.
+            if cpu.is_done() {
+                break;
+            }
+            event!(
+                Level::Debug,
+                "signal",
+                cycle = cycle,
+                x = cpu.x,
+                strength = cpu.signal()
+            );
+            strengths += cpu.signal();
+        }
+        Ok(strengths)
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        let mut cpu = Self::start(input, Part::Two, params)?;
+        run_to_end(&mut cpu);
+        Ok(cpu.screen())
+    }
+}
+
+impl Simulate for Day10 {
+    type State = Cpu;
+
+    /// Both parts run the same program, part 1 samples the signal and part 2 the CRT.
+    fn start(input: &Self::Input, _part: Part, params: &Params) -> Result<Cpu, Box<dyn Error>> {
+        Ok(Cpu::new(input, params.get(&CRT_WIDTH)?.get()))
+    }
+}
+
+/// Generates a program that runs for `size` rows of the CRT, but at least the six of the
+/// puzzle, mostly keeping the sprite on the screen.
+impl Generate for Day10 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        let cycles = 40 * size.max(6);
+        let mut input = String::new();
+        let (mut cycle, mut x) = (0, 1);
+        while cycle < cycles {
+            if cycles - cycle >= 2 && rng.gen_bool(0.7) {
+                let value = rng.gen_range((-1 - x).max(-15)..=(40 - x).min(15));
+                input.push_str(&format!("addx {}\n", value));
+                cycle += 2;
+                x += value;
+            } else {
+                input.push_str("noop\n");
+                cycle += 1;
+            }
+        }
+        input
+    }
+}
+
+/// Every line is an instruction, `noop` or `addx` with a number.
+impl Detect for Day10 {
+    fn detect(&self, sample: &str) -> f64 {
+        share_of_lines(sample, |line| {
+            line == "noop"
+                || line
+                    .strip_prefix("addx ")
+                    .is_some_and(|value| is_digits(value.strip_prefix('-').unwrap_or(value)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simple_cycles() {
+        let instructions = Day10::parse_file("input_test1.txt").unwrap();
+        let mut cpu = Cpu::new(&instructions, 40);
+        let mut values = vec![cpu.x];
+        while !cpu.is_done() {
+            cpu.step();
+            values.push(cpu.x);
+        }
+        // The value during each cycle, and after the last.
+        assert_eq!(values, [1, 1, 1, 4, 4, -1]);
+
+        let padded = Day10::parse_str("noop\n\naddx 3\naddx -5\n\n").unwrap();
+        assert_eq!(padded.len(), instructions.len());
+    }
+
+    #[test]
+    fn many_cycles() {
+        let instructions = Day10::parse_file("input_test2.txt").unwrap();
+        let mut cpu = Cpu::new(&instructions, 40);
+        let mut signals = vec![];
+        for cycle in [20, 60, 100, 140, 180, 220] {
+            let steps = cycle - cpu.cycle;
+            run_steps(&mut cpu, steps);
+            signals.push(cpu.signal());
+        }
+        assert_eq!(signals, [420, 1140, 1800, 2940, 2880, 3960]);
+        assert_eq!(Day10::part1(&instructions).unwrap(), 13140);
+    }
+
+    #[test]
+    fn crt() {
+        let instructions = Day10::parse_file("input_test2.txt").unwrap();
+        let expected_image = vec![
+            "##..##..##..##..##..##..##..##..##..##..",
+            "###...###...###...###...###...###...###.",
+            "####....####....####....####....####....",
+            "#####.....#####.....#####.....#####.....",
+            "######......######......######......####",
+            "#######.......#######.......#######.....",
+        ];
+        let crt_image = Day10::part2(&instructions).unwrap();
+        let rows = crt_image.rows().map(|row| row.iter().collect::<String>());
+        assert_eq!(rows.collect::<Vec<_>>(), expected_image);
+
+        let mut cpu = Cpu::new(&instructions, 40);
+        run_to_end(&mut cpu);
+        assert!(cpu.to_string().ends_with(&crt_image.to_string()), "{}", cpu);
+    }
+
+    #[test]
+    fn unfinished_row_is_padded() {
+        let instructions = Day10::parse_file("input_test1.txt").unwrap();
+        let mut cpu = Cpu::new(&instructions, 4);
+        run_to_end(&mut cpu);
+        // Five cycles draw the first row and one pixel of the second.
+        let screen = cpu.screen();
+        let rows = screen.rows().map(|row| row.iter().collect::<String>());
+        assert_eq!(rows.collect::<Vec<_>>(), ["####", "...."]);
+    }
+}
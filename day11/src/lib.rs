@@ -0,0 +1,785 @@
+use std::{error::Error, fmt, io::BufRead, num::NonZeroUsize, rc::Rc};
+
+use aoc2022::{
+    detect::share_of_lines,
+    event,
+    parser::{
+        alt, blocks, delimited, label, map, number, one_of, pair, position, preceded, separated,
+        tag, try_map, Line, Parser,
+    },
+    simulate::fast_forward,
+    trace::Level,
+    Detect, Diagnostics, Generate, Param, Parameter, Params, ParseError, ParseErrorKind, Part,
+    Simulate, Simulation, Solution, Stepwise, TopK,
+};
+use rand::{seq::SliceRandom, Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+pub struct Day11;
+
+type MonkeyId = usize;
+type ItemId = usize;
+type WorryLevel = usize;
+type Operator = fn(WorryLevel, WorryLevel) -> WorryLevel;
+type Operation = Rc<dyn Fn(WorryLevel) -> WorryLevel>;
+type Target = Rc<dyn Fn(WorryLevel) -> MonkeyId>;
+
+#[derive(Clone)]
+pub struct Monkey {
+    items: Vec<ItemId>,
+    operation: Operation,
+    divisor: WorryLevel,
+    test: Target,
+}
+
+fn next_line<'a, I>(lines: &mut I, expected: &str) -> Result<&'a Line, ParseError>
+where
+    I: Iterator<Item = &'a Line>,
+{
+    lines.next().ok_or_else(|| {
+        ParseError::new(
+            ParseErrorKind::UnexpectedEnd,
+            format!("expected {}", expected),
+        )
+    })
+}
+
+fn parse_starting_items(line: &Line) -> Result<Vec<ItemId>, ParseError> {
+    line.parse(preceded(
+        tag("  Starting items: "),
+        separated(number(), tag(", ")),
+    ))
+}
+
+const OPERATORS: &[(&str, Operator)] = &[
+    ("+", |a, b| a + b),
+    ("-", |a, b| a - b),
+    ("*", |a, b| a * b),
+    ("/", |a, b| a / b),
+];
+
+/// `old`, which is `None`, or a number.
+fn operand<'a>() -> impl Parser<'a, Option<WorryLevel>> {
+    label(
+        alt(map(tag("old"), |_| None), map(number(), Some)),
+        "`old` or a number",
+    )
+}
+
+fn parse_operation(line: &Line) -> Result<Operation, ParseError> {
+    let (operand_a, ((operator_at, operator), (operand_at, operand_b))) = line.parse(preceded(
+        tag("  Operation: new = "),
+        pair(
+            operand(),
+            pair(
+                preceded(tag(" "), pair(position(), one_of(OPERATORS))),
+                preceded(tag(" "), pair(position(), operand())),
+            ),
+        ),
+    ))?;
+    if operator_at.starts_with('/') && operand_b == Some(0) {
+        return Err(line.error_at(
+            operand_at,
+            ParseError::new(ParseErrorKind::InvalidValue, "cannot divide by zero"),
+        ));
+    }
+
+    Ok(Rc::new(move |level| {
+        operator(operand_a.unwrap_or(level), operand_b.unwrap_or(level))
+    }))
+}
+
+fn parse_condition(line: &Line) -> Result<WorryLevel, ParseError> {
+    let divisor = try_map(number(), |divisor: WorryLevel| {
+        if divisor == 0 {
+            Err(ParseError::new(
+                ParseErrorKind::InvalidValue,
+                "cannot divide by zero",
+            ))
+        } else {
+            Ok(divisor)
+        }
+    });
+
+    line.parse(preceded(tag("  Test: divisible by "), divisor))
+}
+
+/// A throw target, along with the error to report if it turns out not to exist.
+type Branch = (bool, MonkeyId, ParseError);
+
+const CONDITIONS: &[(&str, bool)] = &[("true", true), ("false", false)];
+
+fn parse_branch(line: &Line) -> Result<Branch, ParseError> {
+    let (branch, (at, id)) = line.parse(pair(
+        preceded(tag("    If "), one_of(CONDITIONS)),
+        preceded(tag(": throw to monkey "), pair(position(), number())),
+    ))?;
+    let missing = line.error_at(
+        at,
+        ParseError::new(ParseErrorKind::InvalidValue, format!("no monkey {}", id)),
+    );
+
+    Ok((branch, id, missing))
+}
+
+fn parse_test<'a, I>(lines: &mut I) -> Result<(WorryLevel, Target, [Branch; 2]), ParseError>
+where
+    I: Iterator<Item = &'a Line>,
+{
+    let divisor = parse_condition(next_line(lines, "test")?)?;
+    let branch_a = parse_branch(next_line(lines, "branch")?)?;
+    let branch_b = parse_branch(next_line(lines, "branch")?)?;
+
+    let monkeys = match (&branch_a, &branch_b) {
+        ((true, monkey_a, _), (false, monkey_b, _)) => (*monkey_a, *monkey_b),
+        ((false, monkey_a, _), (true, monkey_b, _)) => (*monkey_b, *monkey_a),
+        (_, (_, _, error)) => {
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidValue,
+                "expected one `true` and one `false` branch",
+            )
+            .at(error.line().unwrap(), error.text().unwrap()))
+        }
+    };
+
+    Ok((
+        divisor,
+        Rc::new(move |value| {
+            if value % divisor == 0 {
+                monkeys.0
+            } else {
+                monkeys.1
+            }
+        }),
+        [branch_a, branch_b],
+    ))
+}
+
+fn parse_monkey(block: &[Line], id: MonkeyId) -> Result<(Monkey, [Branch; 2]), ParseError> {
+    let mut lines = block.iter();
+
+    let header = next_line(&mut lines, "monkey header")?;
+    let (at, number) = header.parse(delimited(
+        tag("Monkey "),
+        pair(position(), number::<MonkeyId>()),
+        tag(":"),
+    ))?;
+    if number != id {
+        return Err(header.error_at(
+            at,
+            ParseError::new(
+                ParseErrorKind::InvalidValue,
+                format!("expected monkey {}", id),
+            ),
+        ));
+    }
+    let items = parse_starting_items(next_line(&mut lines, "starting items")?)?;
+    let operation = parse_operation(next_line(&mut lines, "operation")?)?;
+    let (divisor, test, branches) = parse_test(&mut lines)?;
+    if let Some(line) = lines.next() {
+        return Err(ParseError::new(
+            ParseErrorKind::InvalidLine,
+            "expected a blank line before the next monkey",
+        )
+        .at(line.number, &line.text));
+    }
+
+    Ok((
+        Monkey {
+            items,
+            operation,
+            divisor,
+            test,
+        },
+        branches,
+    ))
+}
+
+fn parse_monkeys<R>(reader: R, diagnostics: &mut Diagnostics) -> Result<Vec<Monkey>, Box<dyn Error>>
+where
+    R: BufRead,
+{
+    let reported = diagnostics.errors().len();
+    let blocks = blocks(reader)?;
+    // Monkey business needs monkeys, it is not the empty product.
+    if blocks.is_empty() {
+        return Err(ParseError::new(ParseErrorKind::UnexpectedEnd, "no monkeys").into());
+    }
+    let mut monkeys = vec![];
+    let mut branches = vec![];
+
+    for (id, block) in blocks.iter().enumerate() {
+        if let Some((monkey, monkey_branches)) = diagnostics.check(parse_monkey(block, id)) {
+            monkeys.push(monkey);
+            branches.extend(monkey_branches);
+        }
+    }
+
+    for (_, target, missing) in branches {
+        if target >= blocks.len() {
+            diagnostics.report(missing);
+        }
+    }
+
+    // Monkeys are referred to by position, so a malformed one cannot be skipped; report
+    // all of them, but fail even when parsing leniently.
+    if diagnostics.errors().len() > reported {
+        return Err("monkeys are referred to by position, so none can be skipped".into());
+    }
+
+    Ok(monkeys)
+}
+
+fn turn_with_relief<F>(monkeys: &mut [Monkey], counts: Option<&mut Vec<usize>>, relief: F)
+where
+    F: Fn(WorryLevel) -> WorryLevel,
+{
+    let mut item_inspections = vec![0; monkeys.len()];
+    for id in 0..monkeys.len() {
+        event!(
+            Level::Debug,
+            "turn",
+            monkey = id,
+            items = monkeys[id].items.len()
+        );
+        while !monkeys[id].items.is_empty() {
+            let items = monkeys[id].items.clone();
+            monkeys[id].items.clear();
+            for worry_level in items {
+                item_inspections[id] += 1;
+                let panic_level = monkeys[id].operation.as_ref()(worry_level);
+                let adjusted_level = relief(panic_level);
+                let to_monkey_id = monkeys[id].test.as_ref()(adjusted_level);
+                event!(
+                    Level::Trace,
+                    "inspect",
+                    monkey = id,
+                    worry = worry_level,
+                    adjusted = panic_level,
+                    relieved = adjusted_level,
+                    target = to_monkey_id,
+                );
+                monkeys[to_monkey_id].items.push(adjusted_level);
+            }
+        }
+    }
+    if let Some(v) = counts {
+        for i in 0..v.len() {
+            v[i] += item_inspections[i];
+        }
+    }
+}
+
+fn monkey_business(counts: &[usize]) -> usize {
+    let mut top_two = TopK::largest(2);
+    top_two.extend(counts.iter().copied());
+    top_two.into_sorted_vec().into_iter().product()
+}
+
+/// How worry levels are kept in check after an inspection.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Relief {
+    /// Divided, because the item was not damaged.
+    Divide(WorryLevel),
+    /// Kept modulo a multiple of all divisors, which does not change where items go.
+    Modulo(WorryLevel),
+}
+
+impl Relief {
+    fn apply(self, level: WorryLevel) -> WorryLevel {
+        match self {
+            Relief::Divide(divisor) => level / divisor,
+            Relief::Modulo(modulus) => level % modulus,
+        }
+    }
+}
+
+/// The monkeys playing a number of rounds, one round at a time.
+#[derive(Clone)]
+pub struct Game {
+    monkeys: Vec<Monkey>,
+    relief: Relief,
+    inspections: Vec<usize>,
+    round: usize,
+    rounds: usize,
+}
+
+impl Game {
+    pub fn new(monkeys: &[Monkey], relief: Relief, rounds: usize) -> Self {
+        Self {
+            monkeys: monkeys.to_vec(),
+            relief,
+            inspections: vec![0; monkeys.len()],
+            round: 0,
+            rounds,
+        }
+    }
+
+    pub fn monkey_business(&self) -> usize {
+        monkey_business(&self.inspections)
+    }
+}
+
+/// What changes while the monkeys play, which leaves out their notes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub round: usize,
+    /// The items each monkey holds.
+    pub items: Vec<Vec<ItemId>>,
+    pub inspections: Vec<usize>,
+}
+
+impl Simulation for Game {
+    /// The items each monkey holds, in any order since every item goes its own way.
+    type Key = Vec<Vec<ItemId>>;
+
+    /// Plays a round.
+    fn step(&mut self) {
+        let relief = self.relief;
+        turn_with_relief(&mut self.monkeys, Some(&mut self.inspections), |level| {
+            relief.apply(level)
+        });
+        self.round += 1;
+        event!(
+            Level::Info,
+            "round",
+            number = self.round,
+            inspections = format!("{:?}", self.inspections)
+        );
+    }
+
+    fn is_done(&self) -> bool {
+        self.round == self.rounds
+    }
+
+    fn key(&self) -> Self::Key {
+        self.monkeys
+            .iter()
+            .map(|monkey| {
+                let mut items = monkey.items.clone();
+                items.sort_unstable();
+                items
+            })
+            .collect()
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        Some(self.rounds - self.round)
+    }
+
+    /// Every monkey inspects as many items in every cycle as it did in the last one.
+    fn fast_forward(&mut self, start: &Self, period: usize, cycles: usize) {
+        for (inspections, before) in self.inspections.iter_mut().zip(&start.inspections) {
+            *inspections += (*inspections - before) * cycles;
+        }
+        self.round += period * cycles;
+    }
+}
+
+impl Stepwise for Game {
+    type Snapshot = GameSnapshot;
+
+    fn values(&self) -> Vec<(String, i64)> {
+        let mut values = vec![("round".to_string(), self.round as i64)];
+        for (id, monkey) in self.monkeys.iter().enumerate() {
+            values.push((format!("items{}", id), monkey.items.len() as i64));
+            values.push((format!("inspected{}", id), self.inspections[id] as i64));
+        }
+        values
+    }
+
+    fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            round: self.round,
+            items: self
+                .monkeys
+                .iter()
+                .map(|monkey| monkey.items.clone())
+                .collect(),
+            inspections: self.inspections.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: GameSnapshot) -> Result<(), Box<dyn Error>> {
+        let monkeys = self.monkeys.len();
+        if snapshot.items.len() != monkeys || snapshot.inspections.len() != monkeys {
+            return Err(format!(
+                "the snapshot has {} monkeys holding items and {} inspecting, expected {}",
+                snapshot.items.len(),
+                snapshot.inspections.len(),
+                monkeys
+            )
+            .into());
+        }
+        if snapshot.round > self.rounds {
+            return Err(format!(
+                "the snapshot is after round {}, but there are only {}",
+                snapshot.round, self.rounds
+            )
+            .into());
+        }
+        for (monkey, items) in self.monkeys.iter_mut().zip(snapshot.items) {
+            monkey.items = items;
+        }
+        self.round = snapshot.round;
+        self.inspections = snapshot.inspections;
+        Ok(())
+    }
+}
+
+/// Lists the items and inspections of every monkey, like the puzzle does after a round.
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "after round {} of {}:", self.round, self.rounds)?;
+        for (id, monkey) in self.monkeys.iter().enumerate() {
+            let items = monkey
+                .items
+                .iter()
+                .map(WorryLevel::to_string)
+                .collect::<Vec<_>>();
+            write!(
+                f,
+                "\nMonkey {} inspected items {} times and holds: {}",
+                id,
+                self.inspections[id],
+                items.join(", ")
+            )?;
+        }
+        write!(f, "\nmonkey business: {}", self.monkey_business())
+    }
+}
+
+const ROUNDS: Parameter<usize> = Parameter::new("rounds", "20", "rounds to simulate in part 1");
+
+const RELIEF: Parameter<NonZeroUsize> = Parameter::new(
+    "relief",
+    "3",
+    "divisor of worry levels after an inspection in part 1",
+);
+
+const ROUNDS_WITHOUT_RELIEF: Parameter<usize> = Parameter::new(
+    "rounds_without_relief",
+    "10000",
+    "rounds to simulate in part 2, where worry levels are not divided",
+);
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const PARAMETERS: &'static [&'static dyn Param] = &[&ROUNDS, &RELIEF, &ROUNDS_WITHOUT_RELIEF];
+
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>> {
+        parse_monkeys(reader, diagnostics)
+    }
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Result<Self::Part1, Box<dyn Error>> {
+        let mut game = Self::start(input, Part::One, params)?;
+        fast_forward(&mut game, usize::MAX);
+        Ok(game.monkey_business())
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Result<Self::Part2, Box<dyn Error>> {
+        let mut game = Self::start(input, Part::Two, params)?;
+        fast_forward(&mut game, usize::MAX);
+        Ok(game.monkey_business())
+    }
+}
+
+impl Simulate for Day11 {
+    type State = Game;
+
+    fn start(input: &Self::Input, part: Part, params: &Params) -> Result<Game, Box<dyn Error>> {
+        Ok(match part {
+            Part::One => Game::new(
+                input,
+                Relief::Divide(params.get(&RELIEF)?.get()),
+                params.get(&ROUNDS)?,
+            ),
+            // Without relief worry levels grow unbounded, but since all tests are
+            // divisibility checks we can keep them modulo the product of all divisors.
+            Part::Two => Game::new(
+                input,
+                Relief::Modulo(input.iter().map(|monkey| monkey.divisor).product()),
+                params.get(&ROUNDS_WITHOUT_RELIEF)?,
+            ),
+        })
+    }
+}
+
+/// A monkey as it is generated, before it is written out.
+struct Template {
+    items: Vec<WorryLevel>,
+    /// The operator along with its second operand, or `None` for `old`.
+    operation: (char, Option<WorryLevel>),
+    divisor: WorryLevel,
+    /// Targets if the test is true and false, respectively.
+    targets: [MonkeyId; 2],
+}
+
+/// Whether an item that starts out with `monkey` keeps a worry level that fits for the
+/// twenty rounds of part 1, where levels are not kept modulo the divisors.
+///
+/// Items never affect each other, so this only has to follow a single item around.
+fn stays_in_range(monkeys: &[Template], mut monkey: MonkeyId, mut level: WorryLevel) -> bool {
+    let mut round = 0;
+    while round < 20 {
+        let Template {
+            operation: (operator, operand),
+            divisor,
+            targets,
+            ..
+        } = &monkeys[monkey];
+        let operand = operand.unwrap_or(level);
+        let adjusted = match operator {
+            '*' => level.checked_mul(operand),
+            _ => level.checked_add(operand),
+        };
+        let Some(adjusted) = adjusted else {
+            return false;
+        };
+        level = adjusted / 3;
+        let target = targets[if level.is_multiple_of(*divisor) { 0 } else { 1 }];
+        // Monkeys take turns in order, so an item thrown back waits for the next round.
+        if target < monkey {
+            round += 1;
+        }
+        monkey = target;
+    }
+    true
+}
+
+/// Distinct divisors keep the modulus of part 2 small enough to square worry levels.
+const PRIMES: [WorryLevel; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+/// Generates up to eight monkeys holding `size` items between them.
+///
+/// Operations and items are drawn until no item overflows in part 1, which the puzzle
+/// inputs are crafted to avoid as well.
+impl Generate for Day11 {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+        let count = rng.gen_range(2..=8).min(size.max(2));
+        'monkeys: loop {
+            let mut divisors = PRIMES;
+            divisors.shuffle(rng);
+            let mut monkeys = (0..count)
+                .map(|id| {
+                    let operation = match rng.gen_range(0..4) {
+                        0 => ('*', None),
+                        1 => ('*', Some(rng.gen_range(2..=19))),
+                        _ => ('+', Some(rng.gen_range(1..=8))),
+                    };
+                    let mut others = (0..count).filter(|&other| other != id).collect::<Vec<_>>();
+                    others.shuffle(rng);
+                    Template {
+                        items: vec![],
+                        operation,
+                        divisor: divisors[id],
+                        targets: [others[0], others[others.len() - 1]],
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            for item in 0..size.max(count) {
+                let monkey = if item < count {
+                    item
+                } else {
+                    rng.gen_range(0..count)
+                };
+                let level = (0..100)
+                    .map(|_| rng.gen_range(50..=99))
+                    .find(|&level| stays_in_range(&monkeys, monkey, level));
+                match level {
+                    Some(level) => monkeys[monkey].items.push(level),
+                    None => continue 'monkeys,
+                }
+            }
+
+            return monkeys
+                .iter()
+                .enumerate()
+                .map(|(id, monkey)| {
+                    let items = monkey.items.iter().map(|level| level.to_string());
+                    let (operator, operand) = monkey.operation;
+                    format!(
+                        "Monkey {}:\n  Starting items: {}\n  Operation: new = old {} {}\n  \
+                         Test: divisible by {}\n    If true: throw to monkey {}\n    \
+                         If false: throw to monkey {}\n",
+                        id,
+                        items.collect::<Vec<_>>().join(", "),
+                        operator,
+                        operand.map_or_else(|| "old".to_string(), |value| value.to_string()),
+                        monkey.divisor,
+                        monkey.targets[0],
+                        monkey.targets[1]
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    }
+}
+
+/// Notes on monkeys, every line starts with a known label.
+impl Detect for Day11 {
+    fn detect(&self, sample: &str) -> f64 {
+        const LABELS: [&str; 6] = [
+            "Monkey ",
+            "Starting items:",
+            "Operation:",
+            "Test:",
+            "If true:",
+            "If false:",
+        ];
+        share_of_lines(sample, |line| {
+            LABELS
+                .iter()
+                .any(|label| line.trim_start().starts_with(label))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc2022::{trace, Input, ParseErrors, ParseMode};
+
+    use super::*;
+
+    fn turn(monkeys: &mut [Monkey], counts: Option<&mut Vec<usize>>) {
+        turn_with_relief(monkeys, counts, |level| level / 3);
+    }
+
+    #[test]
+    fn can_parse_monkeys() {
+        let monkeys = Day11::parse_file("test_input.txt").unwrap();
+        assert_eq!(monkeys.len(), 4);
+    }
+
+    #[test]
+    fn result_after_one_turn() {
+        let mut monkeys = Day11::parse_file("test_input.txt").unwrap();
+        turn(&mut monkeys, None);
+        assert_eq!(monkeys[0].items, vec![20, 23, 27, 26]);
+        assert_eq!(monkeys[1].items, vec![2080, 25, 167, 207, 401, 1046]);
+        assert_eq!(monkeys[2].items, vec![]);
+        assert_eq!(monkeys[3].items, vec![]);
+    }
+
+    #[test]
+    fn result_after_twenty_turns() {
+        let mut monkeys = Day11::parse_file("test_input.txt").unwrap();
+        for _ in 0..20 {
+            turn(&mut monkeys, None);
+        }
+        assert_eq!(monkeys[0].items, vec![10, 12, 14, 26, 34]);
+        assert_eq!(monkeys[1].items, vec![245, 93, 53, 199, 115]);
+        assert_eq!(monkeys[2].items, vec![]);
+        assert_eq!(monkeys[3].items, vec![]);
+    }
+
+    #[test]
+    fn inspection_counts() {
+        let mut monkeys = Day11::parse_file("test_input.txt").unwrap();
+        let mut counts = vec![0; monkeys.len()];
+        for _ in 0..20 {
+            turn(&mut monkeys, Some(&mut counts));
+        }
+        assert_eq!(counts, [101, 95, 7, 105]);
+        assert_eq!(monkey_business(&counts), 10605);
+    }
+
+    #[test]
+    fn inspections_are_traced() {
+        let mut monkeys = Day11::parse_file("test_input.txt").unwrap();
+        let ((), events) = trace::collect(Level::Trace, || turn(&mut monkeys, None));
+        let inspections = events
+            .iter()
+            .filter(|event| event.name == "inspect")
+            .collect::<Vec<_>>();
+        assert_eq!(inspections.len(), 14);
+        assert_eq!(
+            inspections[0].to_string(),
+            "[trace] day11: inspect monkey=0 worry=79 adjusted=1501 relieved=500 target=3"
+        );
+        assert_eq!(
+            events.iter().filter(|event| event.name == "turn").count(),
+            4
+        );
+    }
+
+    #[test]
+    fn monkey_business_without_relief() {
+        let monkeys = Day11::parse_file("test_input.txt").unwrap();
+        assert_eq!(Day11::part2(&monkeys).unwrap(), 2713310158);
+    }
+
+    #[test]
+    fn repeating_rounds_are_skipped() {
+        let monkeys = Day11::parse_file("test_input.txt").unwrap();
+        let mut stepped = Day11::start(&monkeys, Part::Two, &Params::default()).unwrap();
+        let mut skipped = stepped.clone();
+        aoc2022::simulate::run_to_end(&mut stepped);
+        assert_eq!(fast_forward(&mut skipped, usize::MAX), 10000);
+        assert_eq!(skipped.inspections, stepped.inspections);
+        assert_eq!(skipped.key(), stepped.key());
+    }
+
+    #[test]
+    fn unknown_throw_target_is_reported_with_position() {
+        let input = std::fs::read_to_string("test_input.txt").unwrap();
+        let input = input.replace("throw to monkey 3", "throw to monkey 7");
+        let err = Day11::parse_str(&input).err().unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let err = &errors.errors()[0];
+        assert_eq!(err.kind(), ParseErrorKind::InvalidValue);
+        assert_eq!((err.line(), err.column()), (Some(6), Some(31)));
+    }
+
+    #[test]
+    fn every_malformed_monkey_is_reported() {
+        let input = std::fs::read_to_string("test_input.txt").unwrap();
+        let input = input
+            .replace("Monkey 1:", "Monkey 5:")
+            .replace("new = old * old", "new = old ^ 2");
+        let err = Day11::parse_str(&input).err().unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let positions = errors
+            .errors()
+            .iter()
+            .map(|err| (err.line(), err.column()))
+            .collect::<Vec<_>>();
+        assert_eq!(positions, [(Some(8), Some(8)), (Some(17), Some(24))]);
+
+        // The monkeys after a malformed one would be numbered wrong, so parsing fails.
+        let mut diagnostics = Diagnostics::new(ParseMode::Lenient);
+        assert!(Day11::parse_input_with(Input::text(&input), &mut diagnostics).is_err());
+        assert_eq!(diagnostics.errors().len(), 2);
+    }
+
+    #[test]
+    fn division_by_zero_is_rejected() {
+        let input = std::fs::read_to_string("test_input.txt").unwrap();
+        let input = input.replace("new = old + 6", "new = old / 0");
+        let err = Day11::parse_str(&input).err().unwrap();
+        let errors = err.downcast_ref::<ParseErrors>().unwrap();
+        let err = &errors.errors()[0];
+        assert_eq!(err.kind(), ParseErrorKind::InvalidValue);
+        assert_eq!((err.line(), err.column()), (Some(10), Some(26)));
+
+        let input = input.replace("new = old / 0", "new = old / 2");
+        assert!(Day11::parse_str(&input).is_ok());
+    }
+
+    #[test]
+    fn empty_input_is_rejected() {
+        for input in ["", "\n\n"] {
+            let err = Day11::parse_str(input).err().unwrap();
+            let errors = err.downcast_ref::<ParseErrors>().unwrap();
+            assert_eq!(errors.errors()[0].kind(), ParseErrorKind::UnexpectedEnd);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::fmt;
+
+use crate::Grid;
+
+/// What kind of value an answer is, so that it can be reported in a machine readable way.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AnswerKind {
+    Integer,
+    Text,
+    /// A grid of cells, rendered as rows separated by newlines.
+    Grid,
+}
+
+impl fmt::Display for AnswerKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Self::Integer => "integer",
+            Self::Text => "text",
+            Self::Grid => "grid",
+        })
+    }
+}
+
+/// A value that solves one part of a puzzle.
+pub trait Answer: fmt::Display {
+    const KIND: AnswerKind;
+}
+
+macro_rules! impl_answer {
+    ($kind:ident: $($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                const KIND: AnswerKind = AnswerKind::$kind;
+            }
+        )*
+    };
+}
+
+impl_answer!(Integer: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_answer!(Text: char, String, &str);
+
+impl<T> Answer for Grid<T>
+where
+    T: fmt::Display,
+{
+    const KIND: AnswerKind = AnswerKind::Grid;
+}
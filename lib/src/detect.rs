@@ -0,0 +1,51 @@
+//! Recognizing which day an input belongs to, to catch mixed up input files early.
+//!
+//! Most parsers skip lines they do not understand, so the input of another day tends to
+//! produce a wrong answer or a panic rather than a parse error.
+
+/// Number of lines of an input that are enough to recognize it.
+pub const SAMPLE_LINES: usize = 100;
+
+/// Recognizes the inputs of a day.
+pub trait Detect {
+    /// How confident we are that `sample`, the first [`SAMPLE_LINES`] lines of an input,
+    /// belongs to this day, from 0 for certainly not to 1 for certainly.
+    fn detect(&self, sample: &str) -> f64;
+}
+
+/// The share of the non-empty lines of `sample` that `matches` accepts, or 0 if there are
+/// none.
+pub fn share_of_lines<F>(sample: &str, matches: F) -> f64
+where
+    F: Fn(&str) -> bool,
+{
+    let (matching, total) = sample
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .fold((0, 0), |(matching, total), line| {
+            (matching + usize::from(matches(line)), total + 1)
+        });
+    if total == 0 {
+        0.0
+    } else {
+        matching as f64 / total as f64
+    }
+}
+
+/// Whether `value` is a non-empty run of ASCII digits.
+pub fn is_digits(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_ignore_empty_lines() {
+        assert_eq!(share_of_lines("1\n\n2\nx\n  \ny\n", is_digits), 0.5);
+        assert_eq!(share_of_lines("\n\n", is_digits), 0.0);
+        assert!(!is_digits(""));
+        assert!(!is_digits("-1"));
+    }
+}
@@ -0,0 +1,344 @@
+use std::fmt;
+
+/// Broad classification of what went wrong while parsing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// A number could not be parsed or does not fit its type.
+    InvalidNumber,
+    /// A token that is not valid at its position.
+    UnexpectedToken,
+    /// A line that does not have the expected shape at all.
+    InvalidLine,
+    /// The input ended before a complete item was read.
+    UnexpectedEnd,
+    /// A command or instruction that is not known.
+    UnknownCommand,
+    /// Syntactically fine, but inconsistent with the rest of the input.
+    InvalidValue,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::InvalidNumber => "invalid number",
+            Self::UnexpectedToken => "unexpected token",
+            Self::InvalidLine => "invalid line",
+            Self::UnexpectedEnd => "unexpected end of input",
+            Self::UnknownCommand => "unknown command",
+            Self::InvalidValue => "invalid value",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// An error in the puzzle input, optionally pointing at where it occurred.
+///
+/// Positions are 1-based. When displayed, errors with a known position are rendered like
+/// a compiler diagnostic, showing the offending line and marking the column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    description: String,
+    file: Option<String>,
+    line: Option<usize>,
+    column: Option<usize>,
+    text: Option<String>,
+}
+
+impl ParseError {
+    pub fn new<S>(kind: ParseErrorKind, description: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            kind,
+            description: description.into(),
+            file: None,
+            line: None,
+            column: None,
+            text: None,
+        }
+    }
+
+    /// Sets the line the error occurred on, along with the contents of that line.
+    pub fn at(mut self, line: usize, text: &str) -> Self {
+        self.line = Some(line);
+        self.text = Some(text.to_string());
+        self
+    }
+
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets the column from a byte offset into the line passed to [`ParseError::at`]; an
+    /// offset inside a character points at that character.
+    pub fn at_offset(self, offset: usize) -> Self {
+        let column = match &self.text {
+            Some(text) => {
+                let mut offset = offset.min(text.len());
+                while !text.is_char_boundary(offset) {
+                    offset -= 1;
+                }
+                text[..offset].chars().count() + 1
+            }
+            None => offset + 1,
+        };
+        self.at_column(column)
+    }
+
+    /// Sets the name of the input the error occurred in, unless one is already known.
+    pub fn in_file<S>(mut self, file: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.file.get_or_insert_with(|| file.into());
+        self
+    }
+
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.description)?;
+
+        let line = match self.line {
+            Some(line) => line,
+            None => {
+                if let Some(file) = &self.file {
+                    write!(f, "\n --> {}", file)?;
+                }
+                return Ok(());
+            }
+        };
+
+        let gutter = " ".repeat(line.to_string().len());
+        write!(
+            f,
+            "\n{}--> {}:{}",
+            gutter,
+            self.file.as_deref().unwrap_or("<input>"),
+            line
+        )?;
+        if let Some(column) = self.column {
+            write!(f, ":{}", column)?;
+        }
+
+        if let Some(text) = &self.text {
+            write!(f, "\n{} |", gutter)?;
+            write!(f, "\n{} | {}", line, text)?;
+            if let Some(column) = self.column {
+                // Columns are 1-based, but a column of 0 still marks the start of the line.
+                write!(
+                    f,
+                    "\n{} | {}^",
+                    gutter,
+                    " ".repeat(column.saturating_sub(1))
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Several errors in the same input, reported together.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseErrors(Vec<ParseError>);
+
+impl ParseErrors {
+    pub fn errors(&self) -> &[ParseError] {
+        &self.0
+    }
+
+    pub(crate) fn in_file(self, file: &str) -> Self {
+        Self(
+            self.0
+                .into_iter()
+                .map(|error| error.in_file(file))
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for ParseErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let [error] = self.0.as_slice() {
+            return write!(f, "{}", error);
+        }
+
+        write!(f, "{} errors in input", self.0.len())?;
+        for error in &self.0 {
+            write!(f, "\n\n{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseErrors {}
+
+/// How parsers treat lines they cannot make sense of.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Malformed lines fail parsing, but only after all of them have been collected.
+    #[default]
+    Strict,
+    /// Malformed lines are skipped, and kept as warnings.
+    Lenient,
+}
+
+/// Collects errors for malformed lines while parsing, so they can be reported together.
+///
+/// Parsers report errors for lines they can skip and carry on with the next line. Errors
+/// that leave no sensible way to continue are returned from the parser directly instead.
+#[derive(Clone, Debug, Default)]
+pub struct Diagnostics {
+    mode: ParseMode,
+    errors: Vec<ParseError>,
+}
+
+impl Diagnostics {
+    pub fn new(mode: ParseMode) -> Self {
+        Self {
+            mode,
+            errors: vec![],
+        }
+    }
+
+    pub fn mode(&self) -> ParseMode {
+        self.mode
+    }
+
+    pub fn report(&mut self, error: ParseError) {
+        self.errors.push(error);
+    }
+
+    /// Unwraps the result of parsing a line, reporting the error if it failed.
+    pub fn check<T>(&mut self, result: Result<T, ParseError>) -> Option<T> {
+        result.map_err(|error| self.report(error)).ok()
+    }
+
+    /// All errors reported so far; in lenient mode, these are the skipped lines.
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    /// Fails with all reported errors, unless none were reported or parsing is lenient.
+    pub fn finish(&mut self) -> Result<(), ParseErrors> {
+        if self.mode == ParseMode::Strict && !self.errors.is_empty() {
+            Err(ParseErrors(std::mem::take(&mut self.errors)))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn in_file(&mut self, file: &str) {
+        for error in &mut self.errors {
+            error.file.get_or_insert_with(|| file.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_description_only() {
+        let error = ParseError::new(ParseErrorKind::UnexpectedEnd, "missing monkeys");
+        assert_eq!(
+            error.to_string(),
+            "unexpected end of input: missing monkeys"
+        );
+    }
+
+    #[test]
+    fn renders_diagnostic() {
+        let error = ParseError::new(ParseErrorKind::InvalidNumber, "expected a count")
+            .at(12, "R x")
+            .at_offset(2)
+            .in_file("input.txt");
+        assert_eq!(error.line(), Some(12));
+        assert_eq!(error.column(), Some(3));
+        assert_eq!(
+            error.to_string(),
+            "invalid number: expected a count\n  \
+             --> input.txt:12:3\n   \
+             |\n\
+             12 | R x\n   \
+             |   ^"
+        );
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let error = ParseError::new(ParseErrorKind::UnexpectedToken, "expected a digit")
+            .at(1, "é x")
+            .at_offset(1);
+        assert_eq!(error.column(), Some(1));
+        let error = error.at_offset(3);
+        assert_eq!(error.column(), Some(3));
+        let error = error.at_offset(100);
+        assert_eq!(error.column(), Some(4));
+
+        let error = error.at_column(0);
+        assert!(error.to_string().ends_with("1 | é x\n  | ^"), "{}", error);
+    }
+
+    #[test]
+    fn strict_diagnostics_fail_with_all_errors() {
+        let mut diagnostics = Diagnostics::new(ParseMode::Strict);
+        assert_eq!(diagnostics.check(Ok::<_, ParseError>(1)), Some(1));
+        assert_eq!(
+            diagnostics.check::<usize>(Err(ParseError::new(ParseErrorKind::InvalidLine, "a"))),
+            None
+        );
+        diagnostics.report(ParseError::new(ParseErrorKind::InvalidLine, "b"));
+        let errors = diagnostics.finish().unwrap_err();
+        assert_eq!(errors.errors().len(), 2);
+        assert!(errors.to_string().starts_with("2 errors in input"));
+    }
+
+    #[test]
+    fn lenient_diagnostics_keep_errors_as_warnings() {
+        let mut diagnostics = Diagnostics::new(ParseMode::Lenient);
+        diagnostics.report(ParseError::new(ParseErrorKind::InvalidLine, "a"));
+        assert!(diagnostics.finish().is_ok());
+        assert_eq!(diagnostics.errors().len(), 1);
+    }
+
+    #[test]
+    fn keeps_first_file_name() {
+        let error = ParseError::new(ParseErrorKind::InvalidLine, "bad")
+            .in_file("a.txt")
+            .in_file("b.txt");
+        assert_eq!(error.file(), Some("a.txt"));
+    }
+}
@@ -0,0 +1,46 @@
+//! Random puzzle inputs, for stress testing solutions at scales far beyond the puzzles.
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+/// Produces valid random inputs for a day.
+///
+/// What `size` counts depends on the day, like elves, moves or directories, but a larger
+/// size always means a larger input.
+pub trait Generate {
+    fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String;
+
+    /// Generates an input from a seed, so that failures can be reproduced.
+    fn generate_seeded(&self, seed: u64, size: usize) -> String {
+        self.generate(&mut StdRng::seed_from_u64(seed), size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+
+    struct Numbers;
+
+    impl Generate for Numbers {
+        fn generate(&self, rng: &mut dyn RngCore, size: usize) -> String {
+            (0..size)
+                .map(|_| format!("{}\n", rng.gen_range(0..1000)))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn seeded_inputs_are_reproducible() {
+        assert_eq!(
+            Numbers.generate_seeded(7, 20),
+            Numbers.generate_seeded(7, 20)
+        );
+        assert_ne!(
+            Numbers.generate_seeded(7, 20),
+            Numbers.generate_seeded(8, 20)
+        );
+        assert_eq!(Numbers.generate_seeded(7, 20).lines().count(), 20);
+    }
+}
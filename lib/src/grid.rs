@@ -0,0 +1,393 @@
+use std::{
+    error::Error,
+    fmt,
+    io::BufRead,
+    iter::{Skip, StepBy},
+    ops::{Index, IndexMut},
+    slice,
+};
+
+use crate::{Diagnostics, ParseError, ParseErrorKind};
+
+/// A position in a grid, as `(row, column)`.
+pub type Position = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A grid of `width` columns from its cells, row by row.
+    ///
+    /// # Panics
+    ///
+    /// If the number of cells is not a multiple of `width`.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "{} cells do not fit in rows of {}",
+            cells.len(),
+            width
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with one row per non-empty line, converting every character with `cell`.
+    ///
+    /// Rows with invalid cells, or a different width than the first row, are reported to
+    /// `diagnostics` and skipped; a grid without any rows is an error.
+    pub fn parse<R, F>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+        mut cell: F,
+    ) -> Result<Self, Box<dyn Error>>
+    where
+        R: BufRead,
+        F: FnMut(char) -> Result<T, ParseError>,
+    {
+        let mut width = None;
+        let mut cells = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let row = line
+                .char_indices()
+                .map(|(offset, ch)| {
+                    cell(ch).map_err(|err| err.at(index + 1, &line).at_offset(offset))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .and_then(|row| match width {
+                    Some(width) if row.len() != width => Err(ParseError::new(
+                        ParseErrorKind::InvalidLine,
+                        format!(
+                            "expected {} cells like the first row, found {}",
+                            width,
+                            row.len()
+                        ),
+                    )
+                    .at(index + 1, &line)),
+                    _ => Ok(row),
+                });
+            if let Some(row) = diagnostics.check(row) {
+                width.get_or_insert(row.len());
+                cells.extend(row);
+            }
+        }
+
+        match width {
+            Some(width) => Ok(Self::from_cells(width, cells)),
+            None => Err(ParseError::new(ParseErrorKind::UnexpectedEnd, "empty grid").into()),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.offset(position).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.offset(position).map(|offset| &mut self.cells[offset])
+    }
+
+    /// All cells, row by row.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    pub fn rows(&self) -> slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of a row, from left to right; reverse it to go from right to left.
+    pub fn row(&self, row: usize) -> slice::Iter<'_, T> {
+        assert!(row < self.height, "row {} out of bounds", row);
+        self.cells[row * self.width..(row + 1) * self.width].iter()
+    }
+
+    /// The cells of a column, from top to bottom; reverse it to go from bottom to top.
+    pub fn column(&self, column: usize) -> StepBy<Skip<slice::Iter<'_, T>>> {
+        assert!(column < self.width, "column {} out of bounds", column);
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    /// The cells on a straight line from `start`, moving by `step` until leaving the grid.
+    ///
+    /// Diagonals are walks with steps like `(1, 1)` and `(1, -1)`.
+    pub fn walk(&self, start: Position, step: (isize, isize)) -> Walk<'_, T> {
+        let mut len = 0;
+        if step != (0, 0) {
+            while self.step(start, (step.0 * len, step.1 * len)).is_some() {
+                len += 1;
+            }
+        }
+        Walk {
+            grid: self,
+            start,
+            step,
+            front: 0,
+            back: len as usize,
+        }
+    }
+
+    /// The positions above, left, right and below `position`, as far as they are in the grid.
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |&offset| self.step(position, offset))
+    }
+
+    /// Like [`Grid::neighbours`], including diagonal neighbours.
+    pub fn all_neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ALL_DIRECTIONS
+            .iter()
+            .filter_map(move |&offset| self.step(position, offset))
+    }
+
+    /// Applies `f` to every cell, keeping the shape of the grid.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn offset(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.0 * self.width + position.1)
+    }
+
+    fn step(&self, (row, column): Position, (rows, columns): (isize, isize)) -> Option<Position> {
+        let position = (
+            row.checked_add_signed(rows)?,
+            column.checked_add_signed(columns)?,
+        );
+        self.contains(position).then_some(position)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} out of bounds for {}x{} grid",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} out of bounds for {}x{} grid",
+                position, width, height
+            )
+        })
+    }
+}
+
+/// Renders the grid row by row, with rows separated by newlines.
+impl<T> fmt::Display for Grid<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the cells on a line through a grid, see [`Grid::walk`].
+#[derive(Clone, Debug)]
+pub struct Walk<'a, T> {
+    grid: &'a Grid<T>,
+    start: Position,
+    step: (isize, isize),
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Walk<'a, T> {
+    fn position_at(&self, index: usize) -> Position {
+        let index = index as isize;
+        (
+            self.start.0.wrapping_add_signed(self.step.0 * index),
+            self.start.1.wrapping_add_signed(self.step.1 * index),
+        )
+    }
+}
+
+impl<'a, T> Iterator for Walk<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(&self.grid[self.position_at(self.front - 1)])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Walk<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(&self.grid[self.position_at(self.back)])
+    }
+}
+
+impl<T> ExactSizeIterator for Walk<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::from_cells(3, (1..=9).collect())
+    }
+
+    #[test]
+    fn parses_rows() {
+        let mut diagnostics = Diagnostics::default();
+        let grid =
+            Grid::parse("#.\n.#\n".as_bytes(), &mut diagnostics, |ch| Ok(ch == '#')).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert!(grid[(1, 1)]);
+        assert!(!grid[(0, 1)]);
+    }
+
+    #[test]
+    fn reports_ragged_rows() {
+        let mut diagnostics = Diagnostics::default();
+        let grid = Grid::parse("12\n123\n45\n".as_bytes(), &mut diagnostics, Ok).unwrap();
+        assert_eq!(grid.to_string(), "12\n45");
+        assert_eq!(diagnostics.errors().len(), 1);
+        assert_eq!(diagnostics.errors()[0].line(), Some(2));
+    }
+
+    #[test]
+    fn indexing_is_bounds_checked() {
+        let grid = digits();
+        assert_eq!(grid.get((1, 2)), Some(&6));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = digits();
+        assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), [4, 5, 6]);
+        assert_eq!(grid.row(1).rev().copied().collect::<Vec<_>>(), [6, 5, 4]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6, 9]);
+        assert_eq!(grid.column(2).rev().copied().collect::<Vec<_>>(), [9, 6, 3]);
+        assert_eq!(
+            grid.walk((0, 0), (1, 1)).copied().collect::<Vec<_>>(),
+            [1, 5, 9]
+        );
+        assert_eq!(
+            grid.walk((0, 2), (1, -1))
+                .rev()
+                .copied()
+                .collect::<Vec<_>>(),
+            [7, 5, 3]
+        );
+        assert_eq!(grid.walk((1, 1), (0, 0)).len(), 0);
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = digits();
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours((1, 1)).count(), 4);
+        assert_eq!(grid.all_neighbours((0, 0)).count(), 3);
+        assert_eq!(grid.all_neighbours((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn renders_rows() {
+        assert_eq!(digits().to_string(), "123\n456\n789");
+    }
+}
@@ -0,0 +1,143 @@
+use std::{
+    convert::Infallible,
+    fmt,
+    fs::File,
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use flate2::bufread::MultiGzDecoder;
+
+/// Where puzzle input is read from.
+///
+/// Paths ending in `.gz` are transparently decompressed while reading, so large inputs
+/// never have to be extracted to disk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input<'a> {
+    File(PathBuf),
+    Gzip(PathBuf),
+    Stdin,
+    Text(&'a str),
+}
+
+impl<'a> Input<'a> {
+    /// Input read from a file, decompressing it if it has a `.gz` extension.
+    pub fn path<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if path.extension().is_some_and(|ext| ext == "gz") {
+            Self::Gzip(path.to_path_buf())
+        } else {
+            Self::File(path.to_path_buf())
+        }
+    }
+
+    /// Input held in memory, e.g. examples in tests.
+    pub fn text(text: &'a str) -> Self {
+        Self::Text(text)
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn BufRead + 'a>> {
+        Ok(match self {
+            Self::File(path) => Box::new(io::BufReader::new(File::open(path)?)),
+            Self::Gzip(path) => Box::new(io::BufReader::new(MultiGzDecoder::new(
+                io::BufReader::new(File::open(path)?),
+            ))),
+            Self::Stdin => Box::new(io::stdin().lock()),
+            Self::Text(text) => Box::new(text.as_bytes()),
+        })
+    }
+
+    pub fn lines(&self) -> io::Result<io::Lines<Box<dyn BufRead + 'a>>> {
+        Ok(self.open()?.lines())
+    }
+}
+
+impl fmt::Display for Input<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::File(path) | Self::Gzip(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "<stdin>"),
+            Self::Text(_) => write!(f, "<text>"),
+        }
+    }
+}
+
+impl FromStr for Input<'_> {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+/// Interprets a command line argument, where `-` denotes stdin and anything else a path.
+impl From<&str> for Input<'_> {
+    fn from(arg: &str) -> Self {
+        if arg == "-" {
+            Self::Stdin
+        } else {
+            Self::path(arg)
+        }
+    }
+}
+
+impl From<&Path> for Input<'_> {
+    fn from(path: &Path) -> Self {
+        Self::path(path)
+    }
+}
+
+impl From<PathBuf> for Input<'_> {
+    fn from(path: PathBuf) -> Self {
+        Self::path(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    #[test]
+    fn detects_gzip_by_extension() {
+        assert_eq!(Input::path("input.txt"), Input::File("input.txt".into()));
+        assert_eq!(
+            Input::path("input.txt.gz"),
+            Input::Gzip("input.txt.gz".into())
+        );
+        assert_eq!("-".parse::<Input>().unwrap(), Input::Stdin);
+    }
+
+    #[test]
+    fn reads_text() {
+        let lines = Input::text("a\nb\n\nc").lines().unwrap();
+        let lines = lines.collect::<io::Result<Vec<_>>>().unwrap();
+        assert_eq!(lines, ["a", "b", "", "c"]);
+    }
+
+    #[test]
+    fn reads_gzip() {
+        let path =
+            std::env::temp_dir().join(format!("aoc2022-input-{}.txt.gz", std::process::id()));
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        encoder.write_all(b"1000\n2000\n").unwrap();
+        encoder.finish().unwrap();
+
+        let mut content = String::new();
+        Input::path(&path)
+            .open()
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(content, "1000\n2000\n");
+    }
+}
@@ -0,0 +1,355 @@
+//! Closed intervals of integers and sets of them, like the section ranges of day 4.
+
+use std::{
+    iter::FromIterator,
+    ops::{Add, Sub},
+    slice,
+};
+
+/// Integer types usable as interval endpoints.
+pub trait Endpoint: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_endpoint {
+    ($($t:ty),*) => {
+        $(
+            impl Endpoint for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+            }
+        )*
+    };
+}
+
+impl_endpoint!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// The integers from `start` to `end`, both included, which is never empty.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T> Interval<T>
+where
+    T: Endpoint,
+{
+    /// The interval from `start` to `end`, or `None` if it would be empty.
+    pub fn new(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    /// The interval of just `value`.
+    pub fn single(value: T) -> Self {
+        Self {
+            start: value,
+            end: value,
+        }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// The number of integers in the interval.
+    pub fn len(&self) -> T {
+        self.end - self.start + T::ONE
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether all of `other` is in this interval.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether the intervals have an integer in common.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Whether the intervals overlap or one ends right before the other starts, so that
+    /// their union is an interval.
+    pub fn touches(&self, other: &Self) -> bool {
+        // Comparing first keeps the additions from overflowing.
+        !(self.end < other.start && self.end + T::ONE < other.start
+            || other.end < self.start && other.end + T::ONE < self.start)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The interval covering both, or `None` if there are integers between them.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.touches(other).then(|| Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    /// The integers of this interval that are not in `other`, which are up to two intervals.
+    pub fn difference(&self, other: &Self) -> IntervalSet<T> {
+        IntervalSet::from(*self).difference(&IntervalSet::from(*other))
+    }
+}
+
+/// A set of integers, kept as sorted intervals that neither overlap nor touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Endpoint,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The intervals of the set in order, with gaps between them.
+    pub fn intervals(&self) -> slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// The number of integers in the set.
+    pub fn covered_len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |len, interval| len + interval.len())
+    }
+
+    /// The intervals of the set that overlap `interval`.
+    fn overlapping(&self, interval: &Interval<T>) -> &[Interval<T>] {
+        let from = self
+            .intervals
+            .partition_point(|other| other.end < interval.start);
+        let until = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end);
+        &self.intervals[from..until.max(from)]
+    }
+
+    /// Adds the integers of `interval`, merging it with the intervals it touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let from = self
+            .intervals
+            .partition_point(|other| other.end < interval.start && !other.touches(&interval));
+        let until = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end || other.touches(&interval));
+        let merged = self.intervals[from..until]
+            .iter()
+            .fold(interval, |merged, other| merged.union(other).unwrap());
+        self.intervals.splice(from..until, [merged]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.overlapping(&Interval::single(value)).len() == 1
+    }
+
+    /// Whether all of `interval` is in the set.
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        matches!(self.overlapping(interval), [other] if other.contains_interval(interval))
+    }
+
+    /// Whether the set has an integer in common with `interval`.
+    pub fn overlaps(&self, interval: &Interval<T>) -> bool {
+        !self.overlapping(interval).is_empty()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let intervals = self
+            .intervals
+            .iter()
+            .flat_map(|interval| {
+                other
+                    .overlapping(interval)
+                    .iter()
+                    .filter_map(|other| interval.intersection(other))
+            })
+            .collect();
+        Self { intervals }
+    }
+
+    /// The integers of this set that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        for interval in &self.intervals {
+            // The start of what is left of `interval`, if anything.
+            let mut start = Some(interval.start);
+            for removed in other.overlapping(interval) {
+                let Some(from) = start else { break };
+                if from < removed.start {
+                    intervals.push(Interval {
+                        start: from,
+                        end: removed.start - T::ONE,
+                    });
+                }
+                start = (removed.end < interval.end).then(|| removed.end + T::ONE);
+            }
+            if let Some(start) = start {
+                intervals.push(Interval {
+                    start,
+                    end: interval.end,
+                });
+            }
+        }
+        Self { intervals }
+    }
+}
+
+impl<T> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self {
+            intervals: vec![interval],
+        }
+    }
+}
+
+/// Collects intervals into a set, merging those that overlap or touch.
+impl<T> FromIterator<Interval<T>> for IntervalSet<T>
+where
+    T: Endpoint,
+{
+    fn from_iter<I>(intervals: I) -> Self
+    where
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        let mut set = Self::new();
+        for interval in intervals {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn interval(start: i32, end: i32) -> Interval<i32> {
+        Interval::new(start, end).unwrap()
+    }
+
+    fn values(set: &IntervalSet<i32>) -> BTreeSet<i32> {
+        set.intervals()
+            .flat_map(|interval| interval.start()..=interval.end())
+            .collect()
+    }
+
+    fn interval_set() -> impl Strategy<Value = IntervalSet<i32>> {
+        prop::collection::vec((-20..20, 0..8), 0..6).prop_map(|intervals| {
+            intervals
+                .into_iter()
+                .map(|(start, len)| interval(start, start + len))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn intervals_compare_like_section_ranges() {
+        let (outer, inner) = (interval(2, 8), interval(3, 7));
+        assert!(outer.contains_interval(&inner) && !inner.contains_interval(&outer));
+        assert!(interval(5, 7).overlaps(&interval(7, 9)));
+        assert!(!interval(2, 3).overlaps(&interval(4, 5)));
+        assert_eq!(interval(2, 3).union(&interval(4, 5)), Some(interval(2, 5)));
+        assert_eq!(interval(2, 3).union(&interval(5, 5)), None);
+        assert_eq!(
+            interval(2, 6).intersection(&interval(4, 8)),
+            Some(interval(4, 6))
+        );
+        assert_eq!(interval(2, 6).len(), 5);
+        assert_eq!(Interval::new(3, 2), None);
+        // Touching is checked without overflowing at the end of the range.
+        let last = Interval::new(u8::MAX - 1, u8::MAX).unwrap();
+        assert!(!last.touches(&Interval::single(0)));
+        assert!(Interval::single(0).union(&last).is_none());
+    }
+
+    #[test]
+    fn sets_merge_overlapping_and_touching_intervals() {
+        let set = [
+            interval(10, 12),
+            interval(1, 3),
+            interval(4, 5),
+            interval(11, 14),
+        ]
+        .into_iter()
+        .collect::<IntervalSet<_>>();
+        assert_eq!(
+            set.intervals().copied().collect::<Vec<_>>(),
+            [interval(1, 5), interval(10, 14)]
+        );
+        assert_eq!(set.covered_len(), 10);
+        assert_eq!(
+            interval(1, 9).difference(&interval(3, 4)),
+            [interval(1, 2), interval(5, 9)].into_iter().collect()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn set_operations_match_sets_of_values(a in interval_set(), b in interval_set()) {
+            let (a_values, b_values) = (values(&a), values(&b));
+            prop_assert_eq!(values(&a.union(&b)), &a_values | &b_values);
+            prop_assert_eq!(values(&a.intersection(&b)), &a_values & &b_values);
+            prop_assert_eq!(values(&a.difference(&b)), &a_values - &b_values);
+            prop_assert_eq!(a.covered_len(), a_values.len() as i32);
+            for value in -25..30 {
+                prop_assert_eq!(a.contains(value), a_values.contains(&value));
+            }
+            for interval in &b {
+                let inside = (interval.start()..=interval.end()).filter(|v| a_values.contains(v));
+                let inside = inside.count() as i32;
+                prop_assert_eq!(a.contains_interval(interval), inside == interval.len());
+                prop_assert_eq!(a.overlaps(interval), inside > 0);
+            }
+            // The intervals stay sorted with gaps between them.
+            for set in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                let intervals = set.intervals().collect::<Vec<_>>();
+                prop_assert!(intervals.windows(2).all(|pair| pair[0].end() + 1 < pair[1].start()));
+            }
+        }
+    }
+}
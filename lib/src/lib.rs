@@ -1,43 +1,261 @@
 use std::fmt;
 use std::{
-    fs::File,
+    any::Any,
+    error::Error,
     io::{self, BufRead},
     path::Path,
+    str::FromStr,
 };
 
-pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+mod answer;
+pub mod detect;
+mod error;
+mod generate;
+pub mod grid;
+mod input;
+pub mod interval;
+pub mod params;
+pub mod parser;
+pub mod simulate;
+mod top_k;
+pub mod trace;
+pub mod vector;
+
+pub use answer::{Answer, AnswerKind};
+pub use detect::Detect;
+pub use error::{Diagnostics, ParseError, ParseErrorKind, ParseErrors, ParseMode};
+pub use generate::Generate;
+pub use grid::Grid;
+pub use input::Input;
+pub use interval::{Interval, IntervalSet};
+pub use params::{Param, Parameter, Params};
+pub use simulate::{Simulate, Simulation, Stepwise};
+pub use top_k::TopK;
+
+pub fn read_lines<'a, I>(input: I) -> io::Result<io::Lines<Box<dyn BufRead + 'a>>>
+where
+    I: Into<Input<'a>>,
+{
+    input.into().lines()
+}
+
+/// The lines of `input` that are not empty. Errors reading a line are passed on, so that
+/// they are not mistaken for the end of the input.
+pub fn read_non_empty_lines<'a, I>(
+    input: I,
+) -> io::Result<impl Iterator<Item = io::Result<String>> + 'a>
 where
-    P: AsRef<Path>,
+    I: Into<Input<'a>>,
 {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    Ok(read_lines(input)?.filter(|line| !line.as_ref().is_ok_and(String::is_empty)))
 }
 
-pub fn read_non_empty_lines<P>(filename: P) -> io::Result<impl Iterator<Item = String>>
+/// Parses a number, reporting failures as [`ParseErrorKind::InvalidNumber`].
+pub fn parse_number<T>(value: &str) -> Result<T, ParseError>
 where
-    P: AsRef<Path>,
+    T: FromStr,
+    T::Err: fmt::Display,
 {
-    Ok(read_lines(filename)?
-        .filter_map(|line| line.ok())
-        .filter(|line| !line.is_empty())
-        .into_iter())
+    value.parse().map_err(|err| {
+        ParseError::new(
+            ParseErrorKind::InvalidNumber,
+            format!("`{}`: {}", value, err),
+        )
+    })
 }
 
-#[derive(Clone, Debug)]
-pub struct ParseError {
-    description: String,
+/// Input named by the first command line argument, falling back to `input.txt`.
+pub fn input_from_args() -> Input<'static> {
+    std::env::args()
+        .nth(1)
+        .map_or_else(|| Input::path("input.txt"), |arg| Input::from(arg.as_str()))
 }
 
-impl ParseError {
-    pub fn new(description: String) -> Self {
-        Self { description }
+/// Wraps up parsing `input`: attributes all errors to it and, when parsing strictly, fails
+/// with every reported error, including the one that aborted parsing, if any.
+fn finish_parse<T>(
+    input: &Input,
+    result: Result<T, Box<dyn Error>>,
+    diagnostics: &mut Diagnostics,
+) -> Result<T, Box<dyn Error>> {
+    let file = input.to_string();
+    let result = result.map_err(|err| match err.downcast::<ParseError>() {
+        Ok(err) => Box::new(err.in_file(file.as_str())) as Box<dyn Error>,
+        Err(err) => match err.downcast::<ParseErrors>() {
+            Ok(errors) => Box::new(errors.in_file(&file)) as Box<dyn Error>,
+            Err(err) => err,
+        },
+    });
+
+    diagnostics.in_file(&file);
+    if diagnostics.mode() == ParseMode::Strict {
+        if let Err(err) = &result {
+            if let Some(err) = err.downcast_ref::<ParseError>() {
+                diagnostics.report(err.clone());
+            } else if let Some(errors) = err.downcast_ref::<ParseErrors>() {
+                errors
+                    .errors()
+                    .iter()
+                    .cloned()
+                    .for_each(|err| diagnostics.report(err));
+            }
+        }
     }
+    diagnostics.finish()?;
+
+    result
 }
 
-impl fmt::Display for ParseError {
+/// Common interface implemented by the solution of every day.
+///
+/// Parsing is separated from solving, so that both parts can be computed on the same
+/// input without reading it twice.
+pub trait Solution {
+    type Input;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    /// Parses the puzzle input. Malformed lines that can be skipped are reported to
+    /// `diagnostics`, errors that make it impossible to continue are returned.
+    fn parse<R: BufRead>(
+        reader: R,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>>;
+
+    /// Named values that tune the solution, like the number of rounds to simulate.
+    const PARAMETERS: &'static [&'static dyn Param] = &[];
+
+    /// Solves part 1, reading the values of [`Solution::PARAMETERS`] from `params`.
+    fn part1_with(input: &Self::Input, params: &Params) -> Result<Self::Part1, Box<dyn Error>>;
+
+    /// Solves part 2, reading the values of [`Solution::PARAMETERS`] from `params`.
+    fn part2_with(input: &Self::Input, params: &Params) -> Result<Self::Part2, Box<dyn Error>>;
+
+    /// Solves part 1 with the default parameters.
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Box<dyn Error>> {
+        Self::part1_with(input, &Params::default())
+    }
+
+    /// Solves part 2 with the default parameters.
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Box<dyn Error>> {
+        Self::part2_with(input, &Params::default())
+    }
+
+    /// Parses the given input strictly, failing with all malformed lines at once.
+    fn parse_input<'a, I>(input: I) -> Result<Self::Input, Box<dyn Error>>
+    where
+        I: Into<Input<'a>>,
+    {
+        Self::parse_input_with(input, &mut Diagnostics::default())
+    }
+
+    /// Parses the given input, attributing parse errors to it.
+    fn parse_input_with<'a, I>(
+        input: I,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self::Input, Box<dyn Error>>
+    where
+        I: Into<Input<'a>>,
+    {
+        let input = input.into();
+        let result = Self::parse(input.open()?, diagnostics);
+        finish_parse(&input, result, diagnostics)
+    }
+
+    fn parse_str(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Self::parse_input(Input::text(input))
+    }
+
+    fn parse_file<P>(filename: P) -> Result<Self::Input, Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+    {
+        Self::parse_input(Input::path(filename))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl fmt::Display for Part {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.description)
+        f.pad(match self {
+            Part::One => "1",
+            Part::Two => "2",
+        })
+    }
+}
+
+/// Type erased view of a [`Solution`], so that days can be driven dynamically.
+///
+/// Implemented for every [`Solution`]; the parsed input is passed around as [`Any`] and
+/// answers are rendered to strings.
+pub trait Solver {
+    fn parse(
+        &self,
+        reader: &mut dyn BufRead,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Box<dyn Any>, Box<dyn Error>>;
+
+    fn solve(&self, input: &dyn Any, part: Part, params: &Params)
+        -> Result<String, Box<dyn Error>>;
+
+    fn parameters(&self) -> &'static [&'static dyn Param];
+
+    /// The kind of answer [`Solver::solve`] renders for `part`.
+    fn answer_kind(&self, part: Part) -> AnswerKind;
+
+    /// Opens and parses the given input, attributing parse errors to it.
+    fn parse_input(
+        &self,
+        input: &Input,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Box<dyn Any>, Box<dyn Error>> {
+        let mut reader = input.open().map_err(|err| format!("{}: {}", input, err))?;
+        let result = self.parse(&mut reader, diagnostics);
+        finish_parse(input, result, diagnostics)
     }
 }
 
-impl std::error::Error for ParseError {}
+impl<S> Solver for S
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn parse(
+        &self,
+        reader: &mut dyn BufRead,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Box<dyn Any>, Box<dyn Error>> {
+        Ok(Box::new(S::parse(reader, diagnostics)?))
+    }
+
+    fn solve(
+        &self,
+        input: &dyn Any,
+        part: Part,
+        params: &Params,
+    ) -> Result<String, Box<dyn Error>> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input was not parsed by this solver");
+        match part {
+            Part::One => Ok(S::part1_with(input, params)?.to_string()),
+            Part::Two => Ok(S::part2_with(input, params)?.to_string()),
+        }
+    }
+
+    fn parameters(&self) -> &'static [&'static dyn Param] {
+        S::PARAMETERS
+    }
+
+    fn answer_kind(&self, part: Part) -> AnswerKind {
+        match part {
+            Part::One => S::Part1::KIND,
+            Part::Two => S::Part2::KIND,
+        }
+    }
+}
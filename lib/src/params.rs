@@ -0,0 +1,160 @@
+//! Named parameters that tune a solution, like the number of rounds to simulate.
+
+use std::{collections::HashMap, error::Error, fmt, marker::PhantomData, str::FromStr};
+
+/// A parameter of type `T`, declared as a constant next to the solution that reads it.
+pub struct Parameter<T> {
+    pub name: &'static str,
+    /// The value used unless another one is given, in the syntax of [`FromStr`].
+    pub default: &'static str,
+    pub description: &'static str,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T> Parameter<T> {
+    pub const fn new(name: &'static str, default: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            default,
+            description,
+            value: PhantomData,
+        }
+    }
+}
+
+/// Type erased view of a [`Parameter`], so that parameters can be listed and checked
+/// without knowing their types.
+pub trait Param {
+    fn name(&self) -> &'static str;
+    fn default_value(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    /// Checks that `value` can be parsed as the type of the parameter.
+    fn check(&self, value: &str) -> Result<(), String>;
+}
+
+impl<T> Param for Parameter<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn default_value(&self) -> &'static str {
+        self.default
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn check(&self, value: &str) -> Result<(), String> {
+        value
+            .parse::<T>()
+            .map(|_| ())
+            .map_err(|err| format!("invalid value `{}` for `{}`: {}", value, self.name, err))
+    }
+}
+
+/// Values given for the parameters of a solution; parameters without one use their default.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(HashMap<String, String>);
+
+impl Params {
+    /// Sets the value of the parameter `name`, which has to be one of `declared` and parse
+    /// as its type.
+    pub fn set(&mut self, declared: &[&dyn Param], name: &str, value: &str) -> Result<(), String> {
+        let parameter = declared
+            .iter()
+            .find(|parameter| parameter.name() == name)
+            .ok_or_else(|| match declared {
+                [] => format!("unknown parameter `{}`, there are none", name),
+                _ => format!(
+                    "unknown parameter `{}`, expected one of {}",
+                    name,
+                    declared
+                        .iter()
+                        .map(|parameter| format!("`{}`", parameter.name()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            })?;
+        parameter.check(value)?;
+        self.0.insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    /// The value of `parameter`, or its default if none was set.
+    pub fn get<T>(&self, parameter: &Parameter<T>) -> Result<T, Box<dyn Error>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self
+            .0
+            .get(parameter.name)
+            .map_or(parameter.default, String::as_str);
+        value.parse().map_err(|err| {
+            format!(
+                "invalid value `{}` for `{}`: {}",
+                value, parameter.name, err
+            )
+            .into()
+        })
+    }
+}
+
+/// A comma separated list of values, like `20,60,100`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct List<T>(pub Vec<T>);
+
+impl<T> FromStr for List<T>
+where
+    T: FromStr,
+{
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|value| value.trim().parse())
+            .collect::<Result<_, _>>()
+            .map(List)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    const ROUNDS: Parameter<usize> = Parameter::new("rounds", "20", "number of rounds");
+    const RELIEF: Parameter<NonZeroUsize> = Parameter::new("relief", "3", "worry divisor");
+    const CYCLES: Parameter<List<usize>> = Parameter::new("cycles", "20, 60", "cycles to sample");
+    const DECLARED: &[&dyn Param] = &[&ROUNDS, &RELIEF, &CYCLES];
+
+    #[test]
+    fn unset_parameters_use_their_default() {
+        let mut params = Params::default();
+        params.set(DECLARED, "rounds", "5").unwrap();
+        assert_eq!(params.get(&ROUNDS).unwrap(), 5);
+        assert_eq!(params.get(&RELIEF).unwrap().get(), 3);
+        assert_eq!(params.get(&CYCLES).unwrap(), List(vec![20, 60]));
+    }
+
+    #[test]
+    fn values_are_checked_when_set() {
+        let mut params = Params::default();
+        assert_eq!(
+            params.set(DECLARED, "relief", "0"),
+            Err("invalid value `0` for `relief`: number would be zero for non-zero type".into())
+        );
+        assert_eq!(
+            params.set(DECLARED, "round", "5"),
+            Err("unknown parameter `round`, expected one of `rounds`, `relief`, `cycles`".into())
+        );
+        assert!(params.set(&[], "round", "5").is_err());
+        assert_eq!(params, Params::default());
+    }
+}
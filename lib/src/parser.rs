@@ -0,0 +1,428 @@
+//! Small parser combinators for line based puzzle input.
+//!
+//! A parser takes the rest of a line and either returns a value along with the input left
+//! after it, or a [`Failure`] holding the input it failed at. [`parse_line`] runs a parser
+//! on a whole line and turns failures into [`ParseError`]s pointing at the right column.
+//!
+//! ```
+//! use aoc2022::parser::{number, parse_line, separated_pair, tag};
+//!
+//! let range = || separated_pair(number::<u32>(), tag("-"), number());
+//! let pair = parse_line(1, "2-4,6-8", separated_pair(range(), tag(","), range())).unwrap();
+//! assert_eq!(pair, ((2, 4), (6, 8)));
+//! ```
+
+use std::{
+    fmt,
+    io::{self, BufRead},
+    str::FromStr,
+};
+
+use crate::{parse_number, ParseError, ParseErrorKind};
+
+/// A failed parse, along with the input that could not be parsed.
+#[derive(Clone, Debug)]
+pub struct Failure<'a> {
+    pub at: &'a str,
+    pub error: Box<ParseError>,
+}
+
+pub type ParseResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+pub trait Parser<'a, T> {
+    fn parse(&self, input: &'a str) -> ParseResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> ParseResult<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> ParseResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Describes the token at the start of `input`, for error messages.
+fn found(input: &str) -> String {
+    if input.is_empty() {
+        return "end of line".to_string();
+    }
+    let len = match input.find(|c: char| !c.is_alphanumeric()) {
+        Some(0) => input.chars().next().map_or(0, char::len_utf8),
+        Some(len) => len,
+        None => input.len(),
+    };
+    format!("`{}`", &input[..len])
+}
+
+/// A [`ParseErrorKind::UnexpectedToken`] failure at `input`.
+pub fn unexpected<'a, E>(input: &'a str, expected: E) -> Failure<'a>
+where
+    E: fmt::Display,
+{
+    Failure {
+        at: input,
+        error: Box::new(ParseError::new(
+            ParseErrorKind::UnexpectedToken,
+            format!("expected {}, found {}", expected, found(input)),
+        )),
+    }
+}
+
+/// Matches `literal` exactly.
+pub fn tag<'a>(literal: &'static str) -> impl Parser<'a, ()> {
+    move |input: &'a str| match input.strip_prefix(literal) {
+        Some(rest) => Ok(((), rest)),
+        None => Err(unexpected(input, format_args!("`{}`", literal))),
+    }
+}
+
+/// A decimal integer, optionally preceded by `-`.
+pub fn number<'a, T>() -> impl Parser<'a, T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    move |input: &'a str| {
+        let sign = usize::from(input.starts_with('-'));
+        let digits = input[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len() - sign);
+        if digits == 0 {
+            return Err(unexpected(input, "a number"));
+        }
+        let (value, rest) = input.split_at(sign + digits);
+        match parse_number(value) {
+            Ok(value) => Ok((value, rest)),
+            Err(error) => Err(Failure {
+                at: input,
+                error: Box::new(error),
+            }),
+        }
+    }
+}
+
+/// A single character matching `predicate`.
+pub fn satisfy<'a, F>(predicate: F, expected: &'static str) -> impl Parser<'a, char>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: &'a str| match input.chars().next() {
+        Some(c) if predicate(c) => Ok((c, &input[c.len_utf8()..])),
+        _ => Err(unexpected(input, expected)),
+    }
+}
+
+/// One of the given literals, returning the value that goes with it.
+pub fn one_of<'a, T>(options: &'static [(&'static str, T)]) -> impl Parser<'a, T>
+where
+    T: Copy,
+{
+    move |input: &'a str| {
+        options
+            .iter()
+            .find_map(|&(literal, value)| Some((value, input.strip_prefix(literal)?)))
+            .ok_or_else(|| {
+                let literals = options
+                    .iter()
+                    .map(|(literal, _)| format!("`{}`", literal))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                unexpected(input, format_args!("one of {}", literals))
+            })
+    }
+}
+
+/// A non-empty run of characters up to the next space.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.find(' ').unwrap_or(input.len()) {
+        0 => Err(unexpected(input, "a word")),
+        len => Ok(input.split_at(len)),
+    }
+}
+
+/// Everything up to the end of the line, possibly nothing.
+pub fn rest<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| Ok((input, &input[input.len()..]))
+}
+
+/// The input at the current position, without consuming any of it; pass it to
+/// [`Line::error_at`] to report errors found after parsing.
+pub fn position<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| Ok((input, input))
+}
+
+pub fn map<'a, T, U, P, F>(parser: P, f: F) -> impl Parser<'a, U>
+where
+    P: Parser<'a, T>,
+    F: Fn(T) -> U,
+{
+    move |input: &'a str| parser.parse(input).map(|(value, rest)| (f(value), rest))
+}
+
+/// Like [`map`], but `f` may reject the value, failing at the start of it.
+pub fn try_map<'a, T, U, P, F>(parser: P, f: F) -> impl Parser<'a, U>
+where
+    P: Parser<'a, T>,
+    F: Fn(T) -> Result<U, ParseError>,
+{
+    move |input: &'a str| {
+        let (value, rest) = parser.parse(input)?;
+        match f(value) {
+            Ok(value) => Ok((value, rest)),
+            Err(error) => Err(Failure {
+                at: input,
+                error: Box::new(error),
+            }),
+        }
+    }
+}
+
+/// Replaces failures at the very start of `parser` with one stating what was expected.
+pub fn label<'a, T, P>(parser: P, expected: &'static str) -> impl Parser<'a, T>
+where
+    P: Parser<'a, T>,
+{
+    move |input: &'a str| {
+        parser.parse(input).map_err(|failure| {
+            if failure.at.len() == input.len()
+                && failure.error.kind() == ParseErrorKind::UnexpectedToken
+            {
+                unexpected(input, expected)
+            } else {
+                failure
+            }
+        })
+    }
+}
+
+pub fn pair<'a, A, B, P, Q>(first: P, second: Q) -> impl Parser<'a, (A, B)>
+where
+    P: Parser<'a, A>,
+    Q: Parser<'a, B>,
+{
+    move |input: &'a str| {
+        let (a, rest) = first.parse(input)?;
+        let (b, rest) = second.parse(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+/// Parses `first` and `second`, keeping only the result of `second`.
+pub fn preceded<'a, A, B, P, Q>(first: P, second: Q) -> impl Parser<'a, B>
+where
+    P: Parser<'a, A>,
+    Q: Parser<'a, B>,
+{
+    map(pair(first, second), |(_, b)| b)
+}
+
+/// Parses `first` and `second`, keeping only the result of `first`.
+pub fn terminated<'a, A, B, P, Q>(first: P, second: Q) -> impl Parser<'a, A>
+where
+    P: Parser<'a, A>,
+    Q: Parser<'a, B>,
+{
+    map(pair(first, second), |(a, _)| a)
+}
+
+/// Parses `parser` between `open` and `close`.
+pub fn delimited<'a, A, T, B, O, P, C>(open: O, parser: P, close: C) -> impl Parser<'a, T>
+where
+    O: Parser<'a, A>,
+    P: Parser<'a, T>,
+    C: Parser<'a, B>,
+{
+    preceded(open, terminated(parser, close))
+}
+
+/// Parses `first` and `second` around `separator`, keeping both results.
+pub fn separated_pair<'a, A, S, B, P, Q, R>(
+    first: P,
+    separator: Q,
+    second: R,
+) -> impl Parser<'a, (A, B)>
+where
+    P: Parser<'a, A>,
+    Q: Parser<'a, S>,
+    R: Parser<'a, B>,
+{
+    pair(first, preceded(separator, second))
+}
+
+/// Tries `first`, then `second`; if both fail, reports the one that got further.
+pub fn alt<'a, T, P, Q>(first: P, second: Q) -> impl Parser<'a, T>
+where
+    P: Parser<'a, T>,
+    Q: Parser<'a, T>,
+{
+    move |input: &'a str| {
+        first.parse(input).or_else(|first_failure| {
+            second.parse(input).map_err(|second_failure| {
+                if first_failure.at.len() < second_failure.at.len() {
+                    first_failure
+                } else {
+                    second_failure
+                }
+            })
+        })
+    }
+}
+
+/// Parses `parser` if it matches; fails only if it fails after consuming input.
+pub fn optional<'a, T, P>(parser: P) -> impl Parser<'a, Option<T>>
+where
+    P: Parser<'a, T>,
+{
+    move |input: &'a str| match parser.parse(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(failure) if failure.at.len() == input.len() => Ok((None, input)),
+        Err(failure) => Err(failure),
+    }
+}
+
+/// One or more `parser`s with `separator` between them.
+pub fn separated<'a, T, S, P, Q>(parser: P, separator: Q) -> impl Parser<'a, Vec<T>>
+where
+    P: Parser<'a, T>,
+    Q: Parser<'a, S>,
+{
+    move |input: &'a str| {
+        let (value, mut input) = parser.parse(input)?;
+        let mut values = vec![value];
+        while let Ok((_, rest)) = separator.parse(input) {
+            let (value, rest) = parser.parse(rest)?;
+            values.push(value);
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+/// Parses all of `line` with `parser`, reporting failures at their position in the line.
+pub fn parse_line<'a, T, P>(line_number: usize, line: &'a str, parser: P) -> Result<T, ParseError>
+where
+    P: Parser<'a, T>,
+{
+    let error_at = |failure: Failure| {
+        (*failure.error)
+            .at(line_number, line)
+            .at_offset(line.len() - failure.at.len())
+    };
+    match parser.parse(line) {
+        Ok((value, "")) => Ok(value),
+        Ok((_, rest)) => Err(error_at(unexpected(rest, "end of line"))),
+        Err(failure) => Err(error_at(failure)),
+    }
+}
+
+/// A line of input along with its 1-based line number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Line {
+    pub number: usize,
+    pub text: String,
+}
+
+impl Line {
+    /// Parses the whole line, see [`parse_line`].
+    pub fn parse<'a, T, P>(&'a self, parser: P) -> Result<T, ParseError>
+    where
+        P: Parser<'a, T>,
+    {
+        parse_line(self.number, &self.text, parser)
+    }
+
+    /// Points `error` at `at`, which must be a suffix of this line, e.g. from [`position`].
+    pub fn error_at(&self, at: &str, error: ParseError) -> ParseError {
+        error
+            .at(self.number, &self.text)
+            .at_offset(self.text.len() - at.len())
+    }
+}
+
+/// Splits the input into blocks of consecutive non-empty lines.
+pub fn blocks<R>(reader: R) -> io::Result<Vec<Vec<Line>>>
+where
+    R: BufRead,
+{
+    let mut blocks = vec![];
+    let mut block = vec![];
+    for (index, text) in reader.lines().enumerate() {
+        let text = text?;
+        if !text.is_empty() {
+            block.push(Line {
+                number: index + 1,
+                text,
+            });
+        } else if !block.is_empty() {
+            blocks.push(std::mem::take(&mut block));
+        }
+    }
+    if !block.is_empty() {
+        blocks.push(block);
+    }
+    Ok(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sequences() {
+        let parser = pair(
+            preceded(tag("move "), number::<u32>()),
+            preceded(tag(" from "), number::<u32>()),
+        );
+        assert_eq!(parse_line(1, "move 3 from 1", parser), Ok((3, 1)));
+    }
+
+    #[test]
+    fn parses_lists() {
+        let parser = preceded(tag("items: "), separated(number::<i64>(), tag(", ")));
+        assert_eq!(parse_line(1, "items: 1, -2, 3", parser), Ok(vec![1, -2, 3]));
+    }
+
+    #[test]
+    fn reports_positions() {
+        let parser = || preceded(tag("items: "), separated(number::<u8>(), tag(", ")));
+        let err = parse_line(4, "items: 1, x", parser()).unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::UnexpectedToken);
+        assert_eq!(err.description(), "expected a number, found `x`");
+        assert_eq!((err.line(), err.column()), (Some(4), Some(11)));
+
+        let err = parse_line(4, "items: 1, 300", parser()).unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::InvalidNumber);
+        assert_eq!(err.column(), Some(11));
+
+        let err = parse_line(4, "items: 1 2", parser()).unwrap_err();
+        assert_eq!(err.description(), "expected end of line, found ` `");
+        assert_eq!(err.column(), Some(9));
+    }
+
+    #[test]
+    fn alternatives() {
+        let operand = || {
+            label(
+                alt(map(tag("old"), |_| None), map(number::<u32>(), Some)),
+                "`old` or a number",
+            )
+        };
+        assert_eq!(parse_line(1, "old", operand()), Ok(None));
+        assert_eq!(parse_line(1, "7", operand()), Ok(Some(7)));
+        let err = parse_line(1, "new", operand()).unwrap_err();
+        assert_eq!(err.description(), "expected `old` or a number, found `new`");
+
+        const OPERATORS: &[(&str, char)] = &[("+", '+'), ("*", '*')];
+        let err = parse_line(1, "-", one_of(OPERATORS)).unwrap_err();
+        assert_eq!(err.description(), "expected one of `+`, `*`, found `-`");
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let blocks = blocks("a\nb\n\n\nc\n".as_bytes()).unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1][0].number, 5);
+        assert_eq!(blocks[1][0].text, "c");
+    }
+}
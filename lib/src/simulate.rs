@@ -0,0 +1,243 @@
+//! Simulations that advance in steps, with drivers that run them and skip over cycles,
+//! and that can be followed one step at a time, like in `aoc repl`.
+
+use std::{collections::HashMap, error::Error, fmt, hash::Hash};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{Params, Part, Solution};
+
+/// The state of a simulation that advances in steps, like the moves of a crane or the
+/// rounds of a game.
+pub trait Simulation {
+    /// What determines how the simulation goes on, for [`fast_forward`] to recognize a
+    /// state it has seen before. Counters that only grow, like the number of inspections,
+    /// are left out, [`Simulation::fast_forward`] accounts for them.
+    type Key: Hash + Eq;
+
+    /// Advances by one step; never called once the simulation is done.
+    fn step(&mut self);
+
+    fn is_done(&self) -> bool;
+
+    fn key(&self) -> Self::Key;
+
+    /// Whether [`fast_forward`] looks up the key of this state among those seen before.
+    ///
+    /// Every state is by default. Simulations whose states can only repeat at some points,
+    /// like the start of a sequence of moves, skip computing the key in between.
+    fn is_checkpoint(&self) -> bool {
+        true
+    }
+
+    /// The number of steps until the simulation is done, if known, so that
+    /// [`fast_forward`] does not skip past the end.
+    fn remaining(&self) -> Option<usize> {
+        None
+    }
+
+    /// Skips `cycles` repetitions of the `period` steps that led from `start`, which has
+    /// the same key, to the current state.
+    ///
+    /// Replays the steps by default, which is correct but not fast.
+    fn fast_forward(&mut self, start: &Self, period: usize, cycles: usize)
+    where
+        Self: Sized,
+    {
+        let _ = start;
+        run_steps(self, period * cycles);
+    }
+}
+
+/// Takes steps until the simulation is done, and returns the number taken.
+pub fn run_to_end<S>(simulation: &mut S) -> usize
+where
+    S: Simulation,
+{
+    run_steps(simulation, usize::MAX)
+}
+
+/// Takes up to `steps` steps, stopping early if the simulation is done, and returns the
+/// number taken.
+pub fn run_steps<S>(simulation: &mut S, steps: usize) -> usize
+where
+    S: Simulation,
+{
+    run_until(simulation, steps, |_| false)
+}
+
+/// Takes steps until `until` holds after a step, the simulation is done or `steps` steps
+/// were taken, and returns the number taken.
+pub fn run_until<S, F>(simulation: &mut S, steps: usize, mut until: F) -> usize
+where
+    S: Simulation,
+    F: FnMut(&S) -> bool,
+{
+    for taken in 0..steps {
+        if simulation.is_done() {
+            return taken;
+        }
+        simulation.step();
+        if until(simulation) {
+            return taken + 1;
+        }
+    }
+    steps
+}
+
+/// Like [`run_steps`], but once a key repeats skips as many repetitions of the steps in
+/// between as fit, with [`Simulation::fast_forward`].
+///
+/// Only the keys are kept, so the state is cloned once, when a cycle is found.
+pub fn fast_forward<S>(simulation: &mut S, steps: usize) -> usize
+where
+    S: Simulation + Clone,
+{
+    let mut seen = HashMap::new();
+    let mut taken = 0;
+    while taken < steps && !simulation.is_done() {
+        let first = if simulation.is_checkpoint() {
+            seen.insert(simulation.key(), taken)
+        } else {
+            None
+        };
+        let Some(first) = first else {
+            simulation.step();
+            taken += 1;
+            continue;
+        };
+        let period = taken - first;
+        let left = simulation
+            .remaining()
+            .map_or(steps - taken, |remaining| remaining.min(steps - taken));
+        let cycles = left / period;
+        if cycles > 0 {
+            // The cycle is found where it ends; repeat it once to have its start at hand.
+            let start = simulation.clone();
+            taken += run_steps(simulation, period);
+            simulation.fast_forward(&start, period, cycles - 1);
+            taken += period * (cycles - 1);
+        }
+        // Too few steps are left for a whole cycle.
+        seen.clear();
+        taken += run_steps(simulation, steps - taken);
+    }
+    taken
+}
+
+/// A [`Simulation`] that can be followed one step at a time, like in `aoc repl`.
+///
+/// [`fmt::Display`] renders the state for people, like a drawing of the crates.
+pub trait Stepwise: Simulation + Clone + fmt::Display {
+    /// What of the state changes while stepping, which is what a snapshot has to hold.
+    type Snapshot: Serialize + DeserializeOwned;
+
+    /// Named numbers that describe the current state, like `("cycle", 20)`, for conditions
+    /// like `cycle >= 20`.
+    fn values(&self) -> Vec<(String, i64)>;
+
+    /// A snapshot of the current state, to resume from with [`Stepwise::restore`].
+    fn snapshot(&self) -> Self::Snapshot;
+
+    /// Resumes from a snapshot of a simulation started on the same input, failing if the
+    /// snapshot cannot belong to it.
+    fn restore(&mut self, snapshot: Self::Snapshot) -> Result<(), Box<dyn Error>>;
+}
+
+/// A [`Solution`] whose parts are simulations that can be followed step by step.
+pub trait Simulate: Solution {
+    type State: Stepwise;
+
+    /// The simulation `part` runs, before its first step.
+    fn start(
+        input: &Self::Input,
+        part: Part,
+        params: &Params,
+    ) -> Result<Self::State, Box<dyn Error>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to `end` modulo 10, summing up the values, which repeats every 10 steps.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Counter {
+        value: u64,
+        sum: u64,
+        steps: usize,
+        end: usize,
+    }
+
+    impl Simulation for Counter {
+        type Key = u64;
+
+        fn step(&mut self) {
+            self.value = (self.value + 1) % 10;
+            self.sum += self.value;
+            self.steps += 1;
+        }
+
+        fn is_done(&self) -> bool {
+            self.steps == self.end
+        }
+
+        fn key(&self) -> u64 {
+            self.value
+        }
+
+        fn remaining(&self) -> Option<usize> {
+            Some(self.end - self.steps)
+        }
+
+        fn fast_forward(&mut self, start: &Self, period: usize, cycles: usize) {
+            self.sum += (self.sum - start.sum) * cycles as u64;
+            self.steps += period * cycles;
+        }
+    }
+
+    fn counter(end: usize) -> Counter {
+        Counter {
+            value: 3,
+            sum: 0,
+            steps: 0,
+            end,
+        }
+    }
+
+    #[test]
+    fn fast_forwarding_matches_stepping() {
+        for (end, steps) in [
+            (1_000_003, 2_000_000),
+            (1_000_003, 999_999),
+            (25, 100),
+            (5, 5),
+        ] {
+            let mut stepped = counter(end);
+            let mut skipped = counter(end);
+            assert_eq!(
+                run_steps(&mut stepped, steps),
+                fast_forward(&mut skipped, steps)
+            );
+            assert_eq!(stepped, skipped);
+        }
+        let mut counter = counter(1_000_000_000_000);
+        assert_eq!(
+            fast_forward(&mut counter, 1_000_000_000_000),
+            1_000_000_000_000
+        );
+        assert_eq!(counter.sum, 4_500_000_000_000);
+    }
+
+    #[test]
+    fn runs_stop_when_conditions_hold() {
+        let mut counter = counter(100);
+        assert_eq!(
+            run_until(&mut counter, 100, |counter| counter.value == 0),
+            7
+        );
+        assert_eq!(run_until(&mut counter, 5, |counter| counter.value == 0), 5);
+        assert_eq!(run_steps(&mut counter, 1000), 88);
+        assert!(counter.is_done());
+    }
+}
@@ -0,0 +1,207 @@
+//! Keeping the largest or smallest `k` of many values, like the elves carrying the most
+//! calories.
+
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// A value along with its key, ordered so that the heap has the worst kept value on top.
+struct Entry<T, K> {
+    key: K,
+    /// How many values were pushed before, so that earlier values win ties.
+    index: usize,
+    smallest: bool,
+    value: T,
+}
+
+impl<T, K> Ord for Entry<T, K>
+where
+    K: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        let by_key = self.key.cmp(&other.key);
+        let by_key = if self.smallest {
+            by_key
+        } else {
+            by_key.reverse()
+        };
+        by_key.then(self.index.cmp(&other.index))
+    }
+}
+
+impl<T, K> PartialOrd for Entry<T, K>
+where
+    K: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, K> PartialEq for Entry<T, K>
+where
+    K: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T, K> Eq for Entry<T, K> where K: Ord {}
+
+/// Collects the `k` values with the largest (or smallest) keys among those pushed, in
+/// O(log k) per value.
+///
+/// Of values with equal keys, those pushed first are kept and come first.
+pub struct TopK<T, K, F> {
+    k: usize,
+    smallest: bool,
+    key: F,
+    pushed: usize,
+    heap: BinaryHeap<Entry<T, K>>,
+}
+
+impl<T> TopK<T, T, fn(&T) -> T>
+where
+    T: Ord + Clone,
+{
+    /// Keeps the `k` largest values.
+    pub fn largest(k: usize) -> Self {
+        Self::largest_by_key(k, T::clone)
+    }
+
+    /// Keeps the `k` smallest values.
+    pub fn smallest(k: usize) -> Self {
+        Self::smallest_by_key(k, T::clone)
+    }
+}
+
+impl<T, K, F> TopK<T, K, F>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    /// Keeps the `k` values with the largest keys.
+    pub fn largest_by_key(k: usize, key: F) -> Self {
+        Self {
+            k,
+            smallest: false,
+            key,
+            pushed: 0,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Keeps the `k` values with the smallest keys.
+    pub fn smallest_by_key(k: usize, key: F) -> Self {
+        Self {
+            smallest: true,
+            ..Self::largest_by_key(k, key)
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        let entry = Entry {
+            key: (self.key)(&value),
+            index: self.pushed,
+            smallest: self.smallest,
+            value,
+        };
+        self.pushed += 1;
+        if self.heap.len() < self.k {
+            self.heap.push(entry);
+        } else if let Some(mut worst) = self.heap.peek_mut() {
+            // The heap is restored once the worst entry is replaced.
+            if entry < *worst {
+                *worst = entry;
+            }
+        }
+    }
+
+    /// The number of values kept, which is at most `k`.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The values kept, best first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|entry| entry.value)
+            .collect()
+    }
+}
+
+impl<T, K, F> Extend<T> for TopK<T, K, F>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    fn extend<I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in values {
+            self.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn ties_are_won_by_earlier_values() {
+        let mut top = TopK::largest_by_key(3, |&(calories, _): &(u32, char)| calories);
+        top.extend([(5, 'a'), (3, 'b'), (5, 'c'), (4, 'd'), (5, 'e'), (5, 'f')]);
+        assert_eq!(top.into_sorted_vec(), [(5, 'a'), (5, 'c'), (5, 'e')]);
+
+        let mut bottom = TopK::smallest_by_key(2, |&(calories, _): &(u32, char)| calories);
+        bottom.extend([(5, 'a'), (3, 'b'), (4, 'c'), (3, 'd'), (3, 'e')]);
+        assert_eq!(bottom.into_sorted_vec(), [(3, 'b'), (3, 'd')]);
+    }
+
+    #[test]
+    fn at_most_k_values_are_kept() {
+        let mut top = TopK::largest(0);
+        top.extend([1, 2, 3]);
+        assert!(top.is_empty());
+        let mut top = TopK::smallest(5);
+        top.extend([0, 2, 1]);
+        assert_eq!(top.len(), 3);
+        assert_eq!(top.into_sorted_vec(), [0, 1, 2]);
+    }
+
+    proptest! {
+        #[test]
+        fn selection_matches_stable_sorting(
+            // Few distinct keys, so that there are many ties.
+            values in prop::collection::vec((0..5_u8, any::<u16>()), 0..40),
+            k in 0..10_usize,
+        ) {
+            let key = |&(key, _): &(u8, u16)| key;
+
+            let mut largest = values.clone();
+            largest.sort_by_key(|value| Reverse(key(value)));
+            largest.truncate(k);
+            let mut top = TopK::largest_by_key(k, key);
+            top.extend(values.iter().copied());
+            prop_assert_eq!(top.into_sorted_vec(), largest);
+
+            let mut smallest = values.clone();
+            smallest.sort_by_key(key);
+            smallest.truncate(k);
+            let mut bottom = TopK::smallest_by_key(k, key);
+            bottom.extend(values.iter().copied());
+            prop_assert_eq!(bottom.into_sorted_vec(), smallest);
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Structured events emitted by solutions, for following along while debugging them.
+//!
+//! Events go to the sink installed on the current thread, if any, so runs are quiet by
+//! default and the fields of an event are only rendered when someone is listening.
+
+use std::{cell::RefCell, fmt, io::Write, rc::Rc, str::FromStr};
+
+/// How detailed an event is; a sink records events up to a maximum level.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Milestones, like the result of a round.
+    Info,
+    /// Steps of a simulation, like a turn or a move.
+    Debug,
+    /// Everything, like every single item or cycle.
+    Trace,
+}
+
+impl Level {
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("info", Self::Info),
+        ("debug", Self::Debug),
+        ("trace", Self::Trace),
+    ];
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (name, _) = Self::NAMES.iter().find(|(_, level)| level == self).unwrap();
+        f.pad(name)
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::NAMES
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|&(_, level)| level)
+            .ok_or_else(|| format!("expected `info`, `debug` or `trace`, found `{}`", s))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    pub level: Level,
+    /// The module that emitted the event, like `day11`.
+    pub target: &'static str,
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl Event {
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.level, self.target, self.name)?;
+        for (key, value) in &self.fields {
+            write!(f, " {}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the events of the thread it is installed on.
+pub trait Sink {
+    fn record(&mut self, event: &Event);
+}
+
+/// Writes every event on a line of its own, like to stderr or to a file.
+pub struct Writer<W>(pub W);
+
+impl<W> Sink for Writer<W>
+where
+    W: Write,
+{
+    fn record(&mut self, event: &Event) {
+        // Tracing is best effort, a failing sink must not fail the solution.
+        let _ = writeln!(self.0, "{}", event);
+    }
+}
+
+/// Keeps events in memory; clones share the same events.
+#[derive(Clone, Debug, Default)]
+pub struct Collector(Rc<RefCell<Vec<Event>>>);
+
+impl Collector {
+    pub fn events(&self) -> Vec<Event> {
+        self.0.borrow().clone()
+    }
+}
+
+impl Sink for Collector {
+    fn record(&mut self, event: &Event) {
+        self.0.borrow_mut().push(event.clone());
+    }
+}
+
+type Installed = Option<(Level, Box<dyn Sink>)>;
+
+thread_local! {
+    static SINK: RefCell<Installed> = const { RefCell::new(None) };
+}
+
+/// Restores the previously installed sink when dropped.
+#[must_use = "the sink is uninstalled again when the guard is dropped"]
+pub struct Guard {
+    previous: Installed,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        SINK.set(self.previous.take());
+    }
+}
+
+/// Sends events up to `level` that are emitted on the current thread to `sink`, until the
+/// returned guard is dropped.
+pub fn install<S>(level: Level, sink: S) -> Guard
+where
+    S: Sink + 'static,
+{
+    let previous = SINK.replace(Some((level, Box::new(sink))));
+    Guard { previous }
+}
+
+/// Runs `f`, collecting the events up to `level` it emits.
+pub fn collect<T, F>(level: Level, f: F) -> (T, Vec<Event>)
+where
+    F: FnOnce() -> T,
+{
+    let collector = Collector::default();
+    let result = {
+        let _guard = install(level, collector.clone());
+        f()
+    };
+    (result, collector.events())
+}
+
+/// Whether events of `level` would be recorded on the current thread.
+pub fn enabled(level: Level) -> bool {
+    SINK.with_borrow(|sink| sink.as_ref().is_some_and(|(max, _)| level <= *max))
+}
+
+/// Records `event` with the sink of the current thread; prefer the [`event!`] macro.
+///
+/// [`event!`]: crate::event
+pub fn emit(event: Event) {
+    SINK.with_borrow_mut(|sink| {
+        if let Some((max, sink)) = sink {
+            if event.level <= *max {
+                sink.record(&event);
+            }
+        }
+    });
+}
+
+/// Emits an event with a name and `key = value` fields, which are only rendered if a sink
+/// on the current thread wants events of that level.
+///
+/// ```
+/// use aoc2022::{event, trace::Level};
+///
+/// event!(Level::Debug, "turn", monkey = 2, items = 4);
+/// ```
+#[macro_export]
+macro_rules! event {
+    ($level:expr, $name:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled($level) {
+            $crate::trace::emit($crate::trace::Event {
+                level: $level,
+                target: module_path!(),
+                name: $name,
+                fields: vec![$((stringify!($key), $value.to_string())),*],
+            });
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_are_filtered_by_level() {
+        let ((), events) = collect(Level::Debug, || {
+            event!(Level::Info, "round", number = 1);
+            event!(Level::Debug, "turn", monkey = 0, items = 2);
+            event!(Level::Trace, "inspect", worry = 79);
+        });
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].target, "aoc2022::trace::tests");
+        assert_eq!(events[1].field("items"), Some("2"));
+        assert_eq!(
+            events[1].to_string(),
+            "[debug] aoc2022::trace::tests: turn monkey=0 items=2"
+        );
+    }
+
+    #[test]
+    fn fields_are_not_rendered_without_a_sink() {
+        struct Panics;
+
+        impl fmt::Display for Panics {
+            fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+                panic!("rendered without a sink");
+            }
+        }
+
+        assert!(!enabled(Level::Info));
+        event!(Level::Info, "quiet", value = Panics);
+    }
+
+    #[test]
+    fn guards_restore_the_previous_sink() {
+        let mut output = vec![];
+        let ((), outer) = collect(Level::Trace, || {
+            let inner = Collector::default();
+            {
+                let _guard = install(Level::Info, inner.clone());
+                event!(Level::Info, "inner");
+            }
+            event!(Level::Info, "outer");
+            assert_eq!(inner.events().len(), 1);
+        });
+        assert_eq!(outer.len(), 1);
+        assert_eq!(outer[0].name, "outer");
+
+        Writer(&mut output).record(&outer[0]);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "[info] aoc2022::trace::tests: outer\n"
+        );
+        assert_eq!("debug".parse::<Level>(), Ok(Level::Debug));
+        assert!("loud".parse::<Level>().is_err());
+    }
+}
@@ -0,0 +1,517 @@
+//! Integer vectors in two and three dimensions, with directions and bounding boxes.
+//!
+//! The y axis points up: [`Vector2::UP`] is `(0, 1)`.
+
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Signed integer types usable as vector coordinates.
+pub trait Coordinate:
+    Copy
+    + Ord
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MINUS_ONE: Self;
+
+    fn abs(self) -> Self;
+
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MINUS_ONE: Self = -1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i8, i16, i32, i64, i128, isize);
+
+/// Operations shared by all vector types, e.g. to build a [`BoundingBox`] of them.
+pub trait Vector: Copy + PartialEq {
+    type Coordinate: Coordinate;
+
+    /// A vector with every coordinate set to `value`.
+    fn splat(value: Self::Coordinate) -> Self;
+
+    /// The coordinate wise minimum of both vectors.
+    fn min_components(self, other: Self) -> Self;
+
+    /// The coordinate wise maximum of both vectors.
+    fn max_components(self, other: Self) -> Self;
+
+    /// The sum of the absolute coordinates.
+    fn manhattan_length(self) -> Self::Coordinate;
+
+    /// The largest absolute coordinate.
+    fn chebyshev_length(self) -> Self::Coordinate;
+}
+
+macro_rules! impl_vector {
+    ($name:ident, $($field:ident),+) => {
+        impl<T> $name<T>
+        where
+            T: Coordinate,
+        {
+            pub const ZERO: Self = Self { $($field: T::ZERO),+ };
+
+            pub fn new($($field: T),+) -> Self {
+                Self { $($field),+ }
+            }
+
+            pub fn abs(self) -> Self {
+                Self { $($field: self.$field.abs()),+ }
+            }
+
+            /// The sign of every coordinate, i.e. a single step towards this vector.
+            pub fn signum(self) -> Self {
+                Self { $($field: self.$field.signum()),+ }
+            }
+
+            pub fn component_mul(self, rhs: Self) -> Self {
+                Self { $($field: self.$field * rhs.$field),+ }
+            }
+
+            pub fn component_div(self, rhs: Self) -> Self {
+                Self { $($field: self.$field / rhs.$field),+ }
+            }
+
+            pub fn manhattan_distance(self, other: Self) -> T {
+                (self - other).manhattan_length()
+            }
+
+            pub fn chebyshev_distance(self, other: Self) -> T {
+                (self - other).chebyshev_length()
+            }
+        }
+
+        impl<T> Vector for $name<T>
+        where
+            T: Coordinate,
+        {
+            type Coordinate = T;
+
+            fn splat(value: T) -> Self {
+                Self { $($field: value),+ }
+            }
+
+            fn min_components(self, other: Self) -> Self {
+                Self { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            fn max_components(self, other: Self) -> Self {
+                Self { $($field: self.$field.max(other.$field)),+ }
+            }
+
+            fn manhattan_length(self) -> T {
+                T::ZERO $(+ self.$field.abs())+
+            }
+
+            fn chebyshev_length(self) -> T {
+                T::ZERO $(.max(self.$field.abs()))+
+            }
+        }
+
+        impl<T> Add for $name<T>
+        where
+            T: Coordinate,
+        {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T> AddAssign for $name<T>
+        where
+            T: Coordinate,
+        {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T> Sub for $name<T>
+        where
+            T: Coordinate,
+        {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T> SubAssign for $name<T>
+        where
+            T: Coordinate,
+        {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<T> Neg for $name<T>
+        where
+            T: Coordinate,
+        {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T> Mul<T> for $name<T>
+        where
+            T: Coordinate,
+        {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T> MulAssign<T> for $name<T>
+        where
+            T: Coordinate,
+        {
+            fn mul_assign(&mut self, rhs: T) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<T> Div<T> for $name<T>
+        where
+            T: Coordinate,
+        {
+            type Output = Self;
+
+            fn div(self, rhs: T) -> Self {
+                Self { $($field: self.$field / rhs),+ }
+            }
+        }
+
+        impl<T> DivAssign<T> for $name<T>
+        where
+            T: Coordinate,
+        {
+            fn div_assign(&mut self, rhs: T) {
+                *self = *self / rhs;
+            }
+        }
+    };
+}
+
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct Vector2<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+impl_vector!(Vector2, x, y);
+
+impl<T> Vector2<T>
+where
+    T: Coordinate,
+{
+    pub const UP: Self = Self {
+        x: T::ZERO,
+        y: T::ONE,
+    };
+    pub const DOWN: Self = Self {
+        x: T::ZERO,
+        y: T::MINUS_ONE,
+    };
+    pub const LEFT: Self = Self {
+        x: T::MINUS_ONE,
+        y: T::ZERO,
+    };
+    pub const RIGHT: Self = Self {
+        x: T::ONE,
+        y: T::ZERO,
+    };
+
+    /// This vector rotated by 90 degrees clockwise.
+    pub fn rotate_right(self) -> Self {
+        Self {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    /// This vector rotated by 90 degrees counterclockwise.
+    pub fn rotate_left(self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+}
+
+impl<T> fmt::Display for Vector2<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, {}", self.x, self.y)
+    }
+}
+
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct Vector3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl_vector!(Vector3, x, y, z);
+
+impl<T> fmt::Display for Vector3<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, {}, {}", self.x, self.y, self.z)
+    }
+}
+
+/// One of the four orthogonal directions, in clockwise order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// A single step in this direction.
+    pub fn to_vector<T>(self) -> Vector2<T>
+    where
+        T: Coordinate,
+    {
+        match self {
+            Self::Up => Vector2::UP,
+            Self::Right => Vector2::RIGHT,
+            Self::Down => Vector2::DOWN,
+            Self::Left => Vector2::LEFT,
+        }
+    }
+}
+
+/// One of the four orthogonal and four diagonal directions, in clockwise order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// The next direction clockwise, i.e. a turn by 45 degrees.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// The next direction counterclockwise, i.e. a turn by 45 degrees.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// A single step in this direction; diagonal steps move along both axes.
+    pub fn to_vector<T>(self) -> Vector2<T>
+    where
+        T: Coordinate,
+    {
+        match self {
+            Self::Up => Vector2::UP,
+            Self::UpRight => Vector2::UP + Vector2::RIGHT,
+            Self::Right => Vector2::RIGHT,
+            Self::DownRight => Vector2::DOWN + Vector2::RIGHT,
+            Self::Down => Vector2::DOWN,
+            Self::DownLeft => Vector2::DOWN + Vector2::LEFT,
+            Self::Left => Vector2::LEFT,
+            Self::UpLeft => Vector2::UP + Vector2::LEFT,
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        match direction {
+            Direction4::Up => Self::Up,
+            Direction4::Right => Self::Right,
+            Direction4::Down => Self::Down,
+            Direction4::Left => Self::Left,
+        }
+    }
+}
+
+/// The smallest box containing a set of points, with inclusive bounds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BoundingBox<V> {
+    pub min: V,
+    pub max: V,
+}
+
+impl<V> BoundingBox<V>
+where
+    V: Vector + Add<Output = V> + Sub<Output = V>,
+{
+    /// A box containing just `point`.
+    pub fn new(point: V) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// The bounding box of all `points`, or `None` if there are none.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = V>,
+    {
+        let mut points = points.into_iter();
+        let mut bounds = Self::new(points.next()?);
+        points.for_each(|point| bounds.include(point));
+        Some(bounds)
+    }
+
+    /// Grows the box to contain `point`.
+    pub fn include(&mut self, point: V) {
+        self.min = self.min.min_components(point);
+        self.max = self.max.max_components(point);
+    }
+
+    pub fn contains(&self, point: V) -> bool {
+        self.min.max_components(point) == point && self.max.min_components(point) == point
+    }
+
+    /// The number of points covered along each axis.
+    pub fn size(&self) -> V {
+        self.max - self.min + V::splat(V::Coordinate::ONE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vector2::new(3, -4);
+        let b = Vector2::new(1, 2);
+        assert_eq!(a + b, Vector2::new(4, -2));
+        assert_eq!(a - b, Vector2::new(2, -6));
+        assert_eq!(-a, Vector2::new(-3, 4));
+        assert_eq!(a * 2, Vector2::new(6, -8));
+        assert_eq!(a / 2, Vector2::new(1, -2));
+        assert_eq!(a.component_mul(b), Vector2::new(3, -8));
+        assert_eq!(a.signum(), Vector2::new(1, -1));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Vector3::new(1_i64, 2, 3);
+        let b = Vector3::new(-2, 2, 7);
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+    }
+
+    #[test]
+    fn rotates_directions() {
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Left.opposite(), Direction4::Right);
+        assert_eq!(Direction8::UpLeft.turn_right(), Direction8::Up);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownRight.opposite(), Direction8::UpLeft);
+        for direction in Direction4::ALL {
+            let step: Vector2 = direction.to_vector();
+            assert_eq!(step.rotate_right(), direction.turn_right().to_vector());
+            assert_eq!(step.rotate_left(), direction.turn_left().to_vector());
+        }
+        assert_eq!(
+            Direction8::DownRight.to_vector::<i32>(),
+            Vector2::new(1, -1)
+        );
+    }
+
+    #[test]
+    fn bounding_box() {
+        let points = [Vector2::new(1, 5), Vector2::new(-2, 3), Vector2::new(4, 4)];
+        let bounds = BoundingBox::from_points(points).unwrap();
+        assert_eq!(bounds.min, Vector2::new(-2, 3));
+        assert_eq!(bounds.max, Vector2::new(4, 5));
+        assert_eq!(bounds.size(), Vector2::new(7, 3));
+        assert!(bounds.contains(Vector2::new(0, 4)));
+        assert!(!bounds.contains(Vector2::new(0, 6)));
+        assert_eq!(BoundingBox::<Vector2>::from_points([]), None);
+    }
+}